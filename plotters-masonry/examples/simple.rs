@@ -2,7 +2,7 @@ use masonry::core::{NewWidget, WidgetId};
 use masonry::theme::default_property_set;
 use masonry_winit::app::{AppDriver, DriverCtx, NewWindow};
 use plotters::prelude::*;
-use plotters_masonry::Plot;
+use plotters_masonry::{Plot, PlotAction};
 use winit::dpi::LogicalSize;
 use winit::window::Window;
 
@@ -14,46 +14,65 @@ impl AppDriver for Driver {
         _window_id: masonry_winit::app::WindowId,
         _ctx: &mut DriverCtx<'_, '_>,
         _widget_id: WidgetId,
-        _action: masonry::core::ErasedAction,
+        action: masonry::core::ErasedAction,
     ) {
+        if let Ok(action) = action.downcast::<PlotAction>() {
+            match *action {
+                PlotAction::Clicked {
+                    data_x,
+                    data_y,
+                    button,
+                } => println!("{button:?} click at x = {data_x:.3}, y = {data_y:.3}"),
+                PlotAction::Hovered(Some(point)) => println!(
+                    "hovering {} at x = {:.3}, y = {:.3}",
+                    point.series_name, point.data_x, point.data_y
+                ),
+                action => println!("{action:?}"),
+            }
+        }
     }
 }
 
 fn main() {
     let plot = Plot::new(
         (),
-        |_size, _, root| {
+        |ctx, _, root| {
             // Code taken from the plotters example: https://github.com/38/plotters#quick-start
-            root.fill(&WHITE).unwrap();
+            root.fill(&WHITE)?;
+            // The plot is drawn in device pixels, the font size is scaled by the widget
+            let px = |logical: f64| (logical * ctx.scale_factor()).round() as u32;
+
             let mut chart = ChartBuilder::on(root)
                 .caption("y=x^2", ("sans-serif", 50).into_font())
-                .margin(5)
-                .margin_right(15)
-                .x_label_area_size(30)
-                .y_label_area_size(30)
-                .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)
-                .unwrap();
+                .margin(px(5.))
+                .margin_right(px(15.))
+                .x_label_area_size(px(30.))
+                .y_label_area_size(px(30.))
+                .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)?;
+
+            ctx.register_chart(&chart);
 
-            chart.configure_mesh().draw().unwrap();
+            chart.configure_mesh().draw()?;
 
+            let legend_width = px(20.) as i32;
+            let points = (-50..=50).map(|x| x as f32 / 50.0).map(|x| (x, x * x));
+            ctx.register_series("y = x^2", points.clone());
             chart
-                .draw_series(LineSeries::new(
-                    (-50..=50).map(|x| x as f32 / 50.0).map(|x| (x, x * x)),
-                    &RED,
-                ))
-                .unwrap()
+                .draw_series(LineSeries::new(points, &RED))?
                 .label("y = x^2")
-                .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + legend_width, y)], RED));
 
             chart
                 .configure_series_labels()
                 .background_style(WHITE.mix(0.8))
                 .border_style(BLACK)
-                .draw()
-                .unwrap();
+                .draw()?;
+            Ok(())
         },
         "simple plot",
-    );
+    )
+    .with_selection()
+    .with_logical_font_size();
 
     let window_size = LogicalSize::new(400., 400.);
     let window_attributes = Window::default_attributes()
@@ -0,0 +1,61 @@
+use std::ops::Range;
+
+use masonry::core::PointerButton;
+
+use crate::Viewport;
+
+/// The actions submitted by a [`Plot`](crate::Plot) widget when the user interacts with it.
+///
+/// Data coordinates can only be reported once the plot function registered its chart with
+/// [`PlotContext::register_chart`](crate::PlotContext::register_chart).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum PlotAction {
+    /// The user clicked on the plot without dragging.
+    Clicked {
+        data_x: f64,
+        data_y: f64,
+        button: PointerButton,
+    },
+    /// The registered point closest to the pointer changed.
+    ///
+    /// This is `None` when the pointer moved away from all points. Points are registered with
+    /// [`PlotContext::register_series`](crate::PlotContext::register_series).
+    Hovered(Option<HoveredPoint>),
+    /// The user selected a rectangular region of the plot, see
+    /// [`Plot::with_selection`](crate::Plot::with_selection).
+    ///
+    /// `points` are the registered points inside the region.
+    RangeSelected {
+        x: Range<f64>,
+        y: Range<f64>,
+        points: Vec<PointIndex>,
+    },
+    /// The user panned or zoomed the plot.
+    ViewportChanged(Viewport),
+    /// The plot function returned an error, which the plot shows instead of the plot.
+    ///
+    /// This is submitted once when the error first occurs, not on every redraw. The plot
+    /// function runs while the plot is painted, when Masonry doesn't let widgets submit actions,
+    /// so the error is submitted on the following animation frame. Errors that first occur
+    /// because the size of the plot or the scale factor of the window changed are submitted on
+    /// the next event.
+    Error(String),
+}
+
+/// The position of a registered point, as the index of its series in the order the series
+/// were registered and the index of the point in the series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointIndex {
+    pub series: usize,
+    pub index: usize,
+}
+
+/// A registered point the pointer is close to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoveredPoint {
+    pub point: PointIndex,
+    pub series_name: String,
+    pub data_x: f64,
+    pub data_y: f64,
+}
@@ -0,0 +1,133 @@
+use masonry::kurbo::Point;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::{CoordMapping, MasonryBackend};
+
+/// The look of the crosshair of a [`Plot`](crate::Plot).
+///
+/// See [`Plot::with_crosshair`](crate::Plot::with_crosshair).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrosshairStyle {
+    /// The color of the crosshair lines.
+    pub line: RGBAColor,
+    /// The fill color of the value labels on the axes.
+    pub background: RGBAColor,
+    /// The color of the text of the value labels.
+    pub text: RGBAColor,
+    /// The font size of the value labels.
+    pub font_size: f64,
+}
+
+impl Default for CrosshairStyle {
+    fn default() -> Self {
+        Self {
+            line: RGBColor(128, 128, 128).to_rgba(),
+            background: RGBColor(41, 41, 41).mix(0.9),
+            text: WHITE.to_rgba(),
+            font_size: 12.,
+        }
+    }
+}
+
+/// The space between the border of a value label and its text.
+const PADDING: i32 = 3;
+
+/// Draw lines through `position` across the chart, with the data coordinates of `position`
+/// in labels next to the x and y axes.
+///
+/// Nothing is drawn when `position` is outside of the chart.
+pub(crate) fn draw(
+    root: &DrawingArea<MasonryBackend, Shift>,
+    style: &CrosshairStyle,
+    mapping: &CoordMapping,
+    position: Point,
+) -> Result<(), DrawingAreaErrorKind<plotters_vello::Error>> {
+    let area = mapping.area();
+    if !area.contains(position) {
+        return Ok(());
+    }
+    let (x, y) = mapping.pixel_to_data(position);
+    let point = (position.x.round() as i32, position.y.round() as i32);
+    let (x0, y0, x1, y1) = (
+        area.x0 as i32,
+        area.y0 as i32,
+        area.x1 as i32,
+        area.y1 as i32,
+    );
+    root.draw(&PathElement::new(
+        [(point.0, y0), (point.0, y1)],
+        style.line,
+    ))?;
+    root.draw(&PathElement::new(
+        [(x0, point.1), (x1, point.1)],
+        style.line,
+    ))?;
+
+    let font = ("sans-serif", style.font_size)
+        .into_font()
+        .color(&style.text);
+    let (root_width, root_height) = root.dim_in_pixel();
+
+    // The x value goes below the x axis, centered on the vertical line
+    let text = format!("{x:.3}");
+    let size = label_size(root, &text, &font)?;
+    let origin = (place(point.0, size.0, root_width as i32), y1);
+    draw_label(root, style, &text, &font, origin, size)?;
+
+    // The y value goes left of the y axis, centered on the horizontal line
+    let text = format!("{y:.3}");
+    let size = label_size(root, &text, &font)?;
+    let origin = (
+        (x0 - size.0).max(0),
+        place(point.1, size.1, root_height as i32),
+    );
+    draw_label(root, style, &text, &font, origin, size)
+}
+
+/// The size of a label showing `text`, including its padding.
+fn label_size(
+    root: &DrawingArea<MasonryBackend, Shift>,
+    text: &str,
+    font: &TextStyle,
+) -> Result<(i32, i32), DrawingAreaErrorKind<plotters_vello::Error>> {
+    let (width, height) = root.estimate_text_size(text, font)?;
+    Ok((width as i32 + 2 * PADDING, height as i32 + 2 * PADDING))
+}
+
+fn draw_label(
+    root: &DrawingArea<MasonryBackend, Shift>,
+    style: &CrosshairStyle,
+    text: &str,
+    font: &TextStyle,
+    origin: (i32, i32),
+    size: (i32, i32),
+) -> Result<(), DrawingAreaErrorKind<plotters_vello::Error>> {
+    let corner = (origin.0 + size.0, origin.1 + size.1);
+    root.draw(&Rectangle::new([origin, corner], style.background.filled()))?;
+    root.draw(&Text::new(
+        text,
+        (origin.0 + PADDING, origin.1 + PADDING),
+        font,
+    ))
+}
+
+/// The start of a label of length `size` centered on `center`, moved so that it stays
+/// between 0 and `limit` if possible.
+fn place(center: i32, size: i32, limit: i32) -> i32 {
+    (center - size / 2).min(limit - size).max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::place;
+
+    #[test]
+    fn labels_stay_inside() {
+        assert_eq!(place(50, 20, 100), 40);
+        assert_eq!(place(5, 20, 100), 0);
+        assert_eq!(place(95, 20, 100), 80);
+        // Labels larger than the widget start at its edge
+        assert_eq!(place(50, 120, 100), 0);
+    }
+}
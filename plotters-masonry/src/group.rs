@@ -0,0 +1,176 @@
+use std::ops::Range;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use masonry::core::WidgetId;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::{CoordMapping, CrosshairStyle, MasonryBackend};
+
+/// Links the x axes and the hover position of several [`Plot`](crate::Plot) widgets.
+///
+/// All plots added to a group with [`Plot::with_group`](crate::Plot::with_group) share the
+/// x range of their viewport, so panning or zooming one of them pans or zooms all of them.
+/// They also show a vertical crosshair at the x position the pointer hovers in any of them.
+/// This requires the plots to have pan and zoom enabled and to register their charts with
+/// [`PlotContext::register_chart`](crate::PlotContext::register_chart).
+///
+/// A plot can't repaint other widgets on its own. When the shared state changes it calls the
+/// wakers the app registered for the other plots with [`PlotGroup::set_waker`]. The xilem
+/// view registers them automatically.
+///
+/// Cloning a group gives another handle to the same group.
+#[derive(Debug, Clone, Default)]
+pub struct PlotGroup {
+    state: Arc<Mutex<GroupState>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct GroupState {
+    /// The shared x range of the viewports, `None` until a plot with pan and zoom joins.
+    pub(crate) x: Option<Range<f64>>,
+    /// The hovered x position in data coordinates.
+    pub(crate) hover_x: Option<f64>,
+    /// Increased whenever `x` or `hover_x` change.
+    pub(crate) generation: u64,
+    members: Vec<WidgetId>,
+    wakers: Vec<(WidgetId, Waker)>,
+}
+
+/// Repaints a plot of a group, see [`PlotGroup::set_waker`].
+#[derive(Clone)]
+struct Waker(Arc<dyn Fn() + Send + Sync>);
+
+impl std::fmt::Debug for Waker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Waker")
+    }
+}
+
+impl GroupState {
+    /// Change the shared x range, returning whether it changed.
+    pub(crate) fn set_x(&mut self, x: Range<f64>) -> bool {
+        if self.x.as_ref() == Some(&x) {
+            return false;
+        }
+        self.x = Some(x);
+        self.generation += 1;
+        true
+    }
+
+    /// Change the hovered x position, returning whether it changed.
+    pub(crate) fn set_hover_x(&mut self, hover_x: Option<f64>) -> bool {
+        if self.hover_x == hover_x {
+            return false;
+        }
+        self.hover_x = hover_x;
+        self.generation += 1;
+        true
+    }
+}
+
+impl PlotGroup {
+    /// Create a new, empty group.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let `wake` repaint the plot with the widget id `id`.
+    ///
+    /// The other plots of the group call `wake` when they change the shared state, it should
+    /// make the app call [`Plot::sync_group`](crate::Plot::sync_group) on the plot, e.g. with
+    /// [`RenderRoot::edit_widget`](masonry::app::RenderRoot::edit_widget) after waking the
+    /// event loop. The xilem view uses this to send a message to the view of the plot. The
+    /// waker is removed when the plot leaves the group.
+    pub fn set_waker(&self, id: WidgetId, wake: impl Fn() + Send + Sync + 'static) {
+        let mut state = self.lock();
+        state.wakers.retain(|(member, _)| *member != id);
+        state.wakers.push((id, Waker(Arc::new(wake))));
+    }
+
+    /// Call the wakers of all plots of the group except `source`.
+    pub(crate) fn wake(&self, source: WidgetId) {
+        let wakers = self.lock().wakers.clone();
+        for (id, Waker(wake)) in wakers {
+            if id != source {
+                wake();
+            }
+        }
+    }
+
+    pub(crate) fn lock(&self) -> MutexGuard<'_, GroupState> {
+        // The state stays consistent even if a plot function panicked while it was locked
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub(crate) fn join(&self, id: WidgetId) {
+        let mut state = self.lock();
+        if !state.members.contains(&id) {
+            state.members.push(id);
+        }
+    }
+
+    pub(crate) fn leave(&self, id: WidgetId) {
+        let mut state = self.lock();
+        state.members.retain(|member| *member != id);
+        state.wakers.retain(|(member, _)| *member != id);
+    }
+}
+
+impl PartialEq for PlotGroup {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl Eq for PlotGroup {}
+
+/// Draw a vertical line across the chart at the data position `x` in the line color of
+/// `style`.
+pub(crate) fn draw_crosshair(
+    root: &DrawingArea<MasonryBackend, Shift>,
+    style: &CrosshairStyle,
+    mapping: &CoordMapping,
+    x: f64,
+) -> Result<(), DrawingAreaErrorKind<plotters_vello::Error>> {
+    let area = mapping.area();
+    let pixel_x = mapping.data_to_pixel((x, 0.)).x;
+    if !(area.x0..=area.x1).contains(&pixel_x) {
+        return Ok(());
+    }
+    let pixel_x = pixel_x.round() as i32;
+    root.draw(&PathElement::new(
+        [(pixel_x, area.y0 as i32), (pixel_x, area.y1 as i32)],
+        style.line,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use masonry::core::WidgetId;
+
+    use super::PlotGroup;
+
+    #[test]
+    fn wake_other_plots() {
+        let group = PlotGroup::new();
+        let (a, b, c) = (WidgetId::next(), WidgetId::next(), WidgetId::next());
+        let wakes = Arc::new([const { AtomicUsize::new(0) }; 3]);
+        for (i, id) in [a, b, c].into_iter().enumerate() {
+            group.join(id);
+            let wakes = wakes.clone();
+            group.set_waker(id, move || {
+                wakes[i].fetch_add(1, Ordering::Relaxed);
+            });
+        }
+
+        group.wake(a);
+        group.leave(c);
+        group.wake(a);
+        let wakes = wakes.each_ref().map(|wakes| wakes.load(Ordering::Relaxed));
+        assert_eq!(wakes, [0, 2, 1]);
+    }
+}
@@ -13,84 +13,221 @@ You'll mainly need [`Plot::new`] from this crate.
 For more complete examples see [the GitHub repo](https://github.com/alexmoon/plotters-xilem)
 
 ```rust
-# use druid::{Widget, WindowDesc, AppLauncher};
-# use plotters_druid::Plot;
+# use plotters_masonry::Plot;
 # use plotters::prelude::*;
-# #[derive(Clone, druid::Data)]
 # struct AppState;
-fn build_plot_widget() -> impl Widget<AppState> {
-    Plot::new(|(width, height), data: &AppState, root| {
-        root.fill(&WHITE).unwrap();
-        let mut chart = ChartBuilder::on(&root)
-            .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)
-            .unwrap();
-
-        // see the plotters documentation on how to use `chart`
-    })
+fn build_plot_widget(state: AppState) -> Plot<AppState> {
+    Plot::new(
+        state,
+        |ctx, data: &mut AppState, root| {
+            root.fill(&WHITE)?;
+            let mut chart = ChartBuilder::on(&root)
+                .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)?;
+
+            // see the plotters documentation on how to use `chart`
+            Ok(())
+        },
+        "plot of my app state",
+    )
 }
-
-# fn main() {
-let main_window = WindowDesc::new(build_plot_widget());
-# }
 ```
 
-# Limitations
+# Errors
 
-It's currently not possible to propagate errors that might be returned from the plotters API. Right now
-this means you'll probably have to use `.unwrap()` a lot in the closure that you pass to [`Plot::new`],
-or alternatively just log it and shrug.
+The closure passed to [`Plot::new`] returns a `Result`, so errors of the plotters API can be propagated
+with `?`. When the closure fails, the widget shows the error message in place of the plot and submits a
+[`PlotAction::Error`] so the rest of the application can react to it. The look of the message can be
+changed with [`Plot::with_error_style`].
 
-The possible errors in there mostly come from the drawing backend, e.g. cairo / direct2d / whatever piet
-uses on your platform. Just directly propagating these in the widget's draw function doesn't really make
-sense because it's not clear what masonry is supposed to do with these errors. Ideally we'd probably change
-something in the data to notify the rest of the application of the error. If anyone has a good suggestion
-for a possible API feel free to open an issue.
+# Device pixels
 
-*/
+The plot is drawn in device pixels, so lines and text snap to the pixels of the display.
+[`PlotContext::size`] is the size of the widget in device pixels and all pixel sizes the closure passes to
+plotters, e.g. margins or the sizes of label areas, are in device pixels as well. Multiply them by
+[`PlotContext::scale_factor`] to make them look the same on all displays, and use
+[`Plot::with_logical_font_size`] to scale the fonts:
+
+```rust
+# use plotters_masonry::Plot;
+# use plotters::prelude::*;
+Plot::new(
+    (),
+    |ctx, _data, root| {
+        let px = |logical: f64| (logical * ctx.scale_factor()).round() as u32;
+        let chart = ChartBuilder::on(&root)
+            .margin(px(5.))
+            .x_label_area_size(px(30.))
+            .y_label_area_size(px(30.))
+            .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)?;
+        Ok(())
+    },
+    "plot",
+)
+.with_logical_font_size();
+```
 
-use std::borrow::Cow;
-use std::cell::RefCell;
-use std::f64::consts::PI;
+This is a breaking change: earlier versions drew the plot in logical pixels, so on displays with a scale
+factor other than 1 plots that use fixed pixel sizes now look smaller or larger than before.
+
+*/
 
 use masonry::core::PaintCtx;
-use masonry::kurbo::{Affine, Vec2};
-use masonry::parley;
+use masonry::parley::FontContext;
 use masonry::vello::Scene;
 use plotters::prelude::*;
-use plotters_backend::text_anchor::{HPos, VPos};
-use plotters_backend::{BackendColor, BackendCoord, DrawingErrorKind};
+use plotters_backend::text_anchor;
+use plotters_backend::{
+    BackendColor, BackendCoord, BackendTextStyle, DrawingErrorKind, FontFamily, FontStyle,
+    FontTransform,
+};
 use plotters_vello::VelloBackend;
-
+pub use plotters_vello::{StrokeStyles, StrokedPrimitive, VelloStrokeStyle};
+
+mod action;
+mod crosshair;
+mod group;
+mod mapping;
+mod placeholder;
+mod selection;
+mod sizing;
+mod tooltip;
+mod viewport;
 mod widget;
 
+pub use action::{HoveredPoint, PlotAction, PointIndex};
+pub use crosshair::CrosshairStyle;
+pub use group::PlotGroup;
+pub use mapping::CoordMapping;
+pub use placeholder::ErrorStyle;
+pub use tooltip::TooltipStyle;
+pub use viewport::Viewport;
 pub use widget::*;
 
 /// The Masonry backend.
 ///
 /// Note that the size of the Masonry scene has to be specified here.
-pub struct MasonryBackend<'a, 'b> {
+pub struct MasonryBackend<'a> {
     vello_backend: VelloBackend<'a>,
-    ctx: RefCell<&'a mut PaintCtx<'b>>,
+    font_scale: f64,
 }
 
-impl std::fmt::Debug for MasonryBackend<'_, '_> {
+impl std::fmt::Debug for MasonryBackend<'_> {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         fmt.debug_struct("MasonryBackend")
             .field("size", &self.vello_backend.get_size())
+            .field("font_scale", &self.font_scale)
             .finish()
     }
 }
 
-impl<'a, 'b> MasonryBackend<'a, 'b> {
-    pub fn new(size: (u32, u32), scene: &'a mut Scene, ctx: &'a mut PaintCtx<'b>) -> Self {
+impl<'a> MasonryBackend<'a> {
+    /// Create a backend drawing into `scene`.
+    ///
+    /// Text is laid out using the fonts known to Masonry.
+    pub fn new(size: (u32, u32), scene: &'a mut Scene, ctx: &'a mut PaintCtx<'_>) -> Self {
+        let (font_ctx, _) = ctx.text_contexts();
+        Self::with_font_context(size, scene, font_ctx)
+    }
+
+    /// Create a backend drawing into `scene`, laying out text with `font_ctx`.
+    pub fn with_font_context(
+        size: (u32, u32),
+        scene: &'a mut Scene,
+        font_ctx: &'a mut FontContext,
+    ) -> Self {
+        Self::from_vello(VelloBackend::with_font_context(size, scene, font_ctx))
+    }
+
+    pub(crate) fn from_vello(vello_backend: VelloBackend<'a>) -> Self {
+        Self {
+            vello_backend,
+            font_scale: 1.,
+        }
+    }
+
+    /// Stroke outlines with `styles`, see [`VelloBackend::with_stroke_styles`].
+    pub fn with_stroke_styles(self, styles: StrokeStyles) -> Self {
         Self {
-            vello_backend: VelloBackend::new(size, scene),
-            ctx: RefCell::new(ctx),
+            vello_backend: self.vello_backend.with_stroke_styles(styles),
+            ..self
         }
     }
+
+    /// Let plotters draw on a finer grid, see [`VelloBackend::with_supersampling`].
+    pub fn with_supersampling(self, factor: u32) -> Self {
+        Self {
+            vello_backend: self.vello_backend.with_supersampling(factor),
+            ..self
+        }
+    }
+
+    /// Collect runs of primitives with the same style into a single path, see
+    /// [`VelloBackend::with_batching`]. The last batch is only drawn by `present`.
+    pub fn with_batching(self, batching: bool) -> Self {
+        Self {
+            vello_backend: self.vello_backend.with_batching(batching),
+            ..self
+        }
+    }
+
+    /// Multiply the size of all fonts by `font_scale`.
+    ///
+    /// The [`Plot`] widget uses this to keep fonts at their logical size when it draws in
+    /// device pixels, see [`Plot::with_logical_font_size`].
+    pub fn with_font_scale(mut self, font_scale: f64) -> Self {
+        self.font_scale = font_scale;
+        self
+    }
 }
 
-impl DrawingBackend for MasonryBackend<'_, '_> {
+/// A text style with its size multiplied by a factor.
+struct ScaledTextStyle<'a, S> {
+    style: &'a S,
+    scale: f64,
+}
+
+impl<S: BackendTextStyle> BackendTextStyle for ScaledTextStyle<'_, S> {
+    type FontError = S::FontError;
+
+    fn color(&self) -> BackendColor {
+        self.style.color()
+    }
+
+    fn size(&self) -> f64 {
+        self.style.size() * self.scale
+    }
+
+    fn transform(&self) -> FontTransform {
+        self.style.transform()
+    }
+
+    fn style(&self) -> FontStyle {
+        self.style.style()
+    }
+
+    fn anchor(&self) -> text_anchor::Pos {
+        self.style.anchor()
+    }
+
+    fn family(&self) -> FontFamily<'_> {
+        self.style.family()
+    }
+
+    fn layout_box(&self, text: &str) -> Result<((i32, i32), (i32, i32)), Self::FontError> {
+        self.style.layout_box(text)
+    }
+
+    fn draw<E, DrawFunc: FnMut(i32, i32, BackendColor) -> Result<(), E>>(
+        &self,
+        text: &str,
+        pos: BackendCoord,
+        draw: DrawFunc,
+    ) -> Result<Result<(), E>, Self::FontError> {
+        self.style.draw(text, pos, draw)
+    }
+}
+
+impl DrawingBackend for MasonryBackend<'_> {
     type ErrorType = plotters_vello::Error;
 
     fn get_size(&self) -> (u32, u32) {
@@ -98,11 +235,11 @@ impl DrawingBackend for MasonryBackend<'_, '_> {
     }
 
     fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        Ok(())
+        self.vello_backend.ensure_prepared()
     }
 
     fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        Ok(())
+        self.vello_backend.present()
     }
 
     fn draw_pixel(
@@ -156,7 +293,7 @@ impl DrawingBackend for MasonryBackend<'_, '_> {
         vert: I,
         style: &S,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        self.vello_backend.draw_path(vert, style)
+        self.vello_backend.fill_polygon(vert, style)
     }
 
     fn draw_text<TStyle: plotters_backend::BackendTextStyle>(
@@ -165,68 +302,11 @@ impl DrawingBackend for MasonryBackend<'_, '_> {
         style: &TStyle,
         pos: BackendCoord,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        use masonry::core::render_text;
-        use parley::{FontStack, FontWeight, GenericFamily, PlainEditor, StyleProperty};
-
-        let mut editor = PlainEditor::new(style.size() as f32);
-        editor.set_text(text);
-
-        let styles = editor.edit_styles();
-        let family = match style.family() {
-            FontFamily::Serif => parley::FontFamily::Generic(GenericFamily::UiSerif),
-            FontFamily::SansSerif => parley::FontFamily::Generic(GenericFamily::UiSansSerif),
-            FontFamily::Monospace => parley::FontFamily::Generic(GenericFamily::UiMonospace),
-            FontFamily::Name(name) => parley::FontFamily::Named(Cow::Owned(name.to_owned())),
-        };
-        styles.insert(StyleProperty::FontStack(FontStack::Single(family)));
-        styles.insert(match style.style() {
-            FontStyle::Normal => StyleProperty::FontStyle(parley::FontStyle::Normal),
-            FontStyle::Oblique => StyleProperty::FontStyle(parley::FontStyle::Oblique(None)),
-            FontStyle::Italic => StyleProperty::FontStyle(parley::FontStyle::Italic),
-            FontStyle::Bold => StyleProperty::FontWeight(FontWeight::BOLD),
-        });
-
-        let mut ctx = self.ctx.borrow_mut();
-        let (font_ctx, layout_ctx) = ctx.text_contexts();
-        let layout = editor.layout(font_ctx, layout_ctx);
-        let (width, height) = (f64::from(layout.full_width()), f64::from(layout.height()));
-
-        // Center on the origin
-        let transform = Affine::translate(Vec2::new(-width / 2., -height / 2.));
-
-        // Rotate based on FontTransform
-        let (width, height, transform) = match style.transform() {
-            FontTransform::None => (width, height, transform),
-            FontTransform::Rotate90 => (height, width, transform.then_rotate(PI / 2.)),
-            FontTransform::Rotate180 => (width, height, transform.then_rotate(PI)),
-            FontTransform::Rotate270 => (height, width, transform.then_rotate(-PI / 2.)),
-        };
-
-        // Move the anchor to the origin
-        let transform = match style.anchor().h_pos {
-            HPos::Left => transform.then_translate(Vec2::new(width / 2., 0.)),
-            HPos::Center => transform,
-            HPos::Right => transform.then_translate(Vec2::new(-width / 2., 0.)),
+        let style = ScaledTextStyle {
+            style,
+            scale: self.font_scale,
         };
-        let transform = match style.anchor().v_pos {
-            VPos::Top => transform.then_translate(Vec2::new(0., height / 2.)),
-            VPos::Center => transform,
-            VPos::Bottom => transform.then_translate(Vec2::new(0., -height / 2.)),
-        };
-
-        // Move to pos
-        let transform = transform.then_translate(Vec2::new(f64::from(pos.0), f64::from(pos.1)));
-
-        let color = plotters_vello::plotters_color_to_peniko(&style.color());
-        render_text(
-            self.vello_backend.scene(),
-            transform,
-            layout,
-            &[color.into()],
-            true,
-        );
-
-        Ok(())
+        self.vello_backend.draw_text(text, &style, pos)
     }
 
     fn estimate_text_size<TStyle: plotters_backend::BackendTextStyle>(
@@ -234,38 +314,11 @@ impl DrawingBackend for MasonryBackend<'_, '_> {
         text: &str,
         style: &TStyle,
     ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
-        use parley::{FontStack, FontWeight, GenericFamily, PlainEditor, StyleProperty};
-
-        let mut editor = PlainEditor::new(style.size() as f32);
-        editor.set_text(text);
-
-        let styles = editor.edit_styles();
-        let family = match style.family() {
-            FontFamily::Serif => parley::FontFamily::Generic(GenericFamily::UiSerif),
-            FontFamily::SansSerif => parley::FontFamily::Generic(GenericFamily::UiSansSerif),
-            FontFamily::Monospace => parley::FontFamily::Generic(GenericFamily::UiMonospace),
-            FontFamily::Name(name) => parley::FontFamily::Named(Cow::Owned(name.to_owned())),
+        let style = ScaledTextStyle {
+            style,
+            scale: self.font_scale,
         };
-        styles.insert(StyleProperty::FontStack(FontStack::Single(family)));
-        styles.insert(match style.style() {
-            FontStyle::Normal => StyleProperty::FontStyle(parley::FontStyle::Normal),
-            FontStyle::Oblique => StyleProperty::FontStyle(parley::FontStyle::Oblique(None)),
-            FontStyle::Italic => StyleProperty::FontStyle(parley::FontStyle::Italic),
-            FontStyle::Bold => StyleProperty::FontWeight(FontWeight::BOLD),
-        });
-
-        let mut ctx = self.ctx.borrow_mut();
-        let (font_ctx, layout_ctx) = ctx.text_contexts();
-        let layout = editor.layout(font_ctx, layout_ctx);
-        let (width, height) = (
-            layout.full_width().ceil() as u32,
-            layout.height().ceil() as u32,
-        );
-
-        Ok(match style.transform() {
-            FontTransform::None | FontTransform::Rotate180 => (width, height),
-            FontTransform::Rotate90 | FontTransform::Rotate270 => (height, width),
-        })
+        self.vello_backend.estimate_text_size(text, &style)
     }
 
     fn blit_bitmap(
@@ -277,3 +330,42 @@ impl DrawingBackend for MasonryBackend<'_, '_> {
         self.vello_backend.blit_bitmap(pos, size, src)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use plotters::coord::Shift;
+
+    use super::*;
+
+    fn draw_polygons<DB: DrawingBackend>(root: DrawingArea<DB, Shift>) {
+        root.draw(&Polygon::new(
+            vec![(10, 10), (90, 20), (50, 80)],
+            RED.filled(),
+        ))
+        .unwrap();
+        root.draw(&Polygon::new(
+            vec![(0, 50), (100, 50), (100, 100), (0, 100)],
+            BLUE.mix(0.5).filled(),
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn fill_polygon_matches_vello() {
+        let mut vello_scene = Scene::new();
+        draw_polygons(VelloBackend::new((100, 100), &mut vello_scene).into_drawing_area());
+
+        let mut masonry_scene = Scene::new();
+        draw_polygons(
+            MasonryBackend::from_vello(VelloBackend::new((100, 100), &mut masonry_scene))
+                .into_drawing_area(),
+        );
+
+        let (vello, masonry) = (vello_scene.encoding(), masonry_scene.encoding());
+        assert!(vello.path_tags == masonry.path_tags);
+        assert_eq!(vello.path_data, masonry.path_data);
+        assert!(vello.draw_tags == masonry.draw_tags);
+        assert_eq!(vello.draw_data, masonry.draw_data);
+        assert_eq!(vello.styles, masonry.styles);
+    }
+}
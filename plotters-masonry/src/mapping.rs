@@ -0,0 +1,151 @@
+use std::ops::Range;
+
+use masonry::kurbo::{Point, Rect};
+use plotters::coord::ranged1d::Ranged;
+use plotters::prelude::{Cartesian2d, ChartContext, DrawingBackend};
+
+/// The mapping between widget pixels and data coordinates of a chart.
+///
+/// The plot function registers it with [`PlotContext::register_chart`](crate::PlotContext::register_chart),
+/// after which it's available from [`Plot::coord_mapping`](crate::Plot::coord_mapping).
+/// Both axes are assumed to be linear.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoordMapping {
+    area: Rect,
+    x: Range<f64>,
+    y: Range<f64>,
+}
+
+impl CoordMapping {
+    /// Create a mapping of the data ranges `x` and `y` onto `area`.
+    ///
+    /// `x.start` is mapped onto the left edge of `area` and `y.start` onto its bottom edge.
+    pub fn new(area: Rect, x: Range<f64>, y: Range<f64>) -> Self {
+        Self { area, x, y }
+    }
+
+    /// Create the mapping used by a chart with cartesian coordinates.
+    pub fn from_chart<DB, X, Y>(chart: &ChartContext<'_, DB, Cartesian2d<X, Y>>) -> Self
+    where
+        DB: DrawingBackend,
+        X: Ranged,
+        Y: Ranged,
+        X::ValueType: Into<f64>,
+        Y::ValueType: Into<f64>,
+    {
+        let (x_pixels, y_pixels) = chart.plotting_area().get_pixel_range();
+        // Plotters maps the ranges onto the pixel centers at the edges of the plotting area
+        let area = Rect::new(
+            f64::from(x_pixels.start),
+            f64::from(y_pixels.start),
+            f64::from(x_pixels.end - 1),
+            f64::from(y_pixels.end - 1),
+        );
+
+        Self::new(
+            area,
+            range_into_f64(chart.x_range()),
+            range_into_f64(chart.y_range()),
+        )
+    }
+
+    /// The same mapping with the pixel positions multiplied by `factor`.
+    pub(crate) fn scaled(&self, factor: f64) -> Self {
+        Self::new(
+            self.area.scale_from_origin(factor),
+            self.x.clone(),
+            self.y.clone(),
+        )
+    }
+
+    /// The area of the widget covered by the chart, in widget pixels.
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// The range of the x axis.
+    pub fn x_range(&self) -> Range<f64> {
+        self.x.clone()
+    }
+
+    /// The range of the y axis.
+    pub fn y_range(&self) -> Range<f64> {
+        self.y.clone()
+    }
+
+    /// Convert a position in the widget to data coordinates.
+    ///
+    /// Positions outside of [`area`](Self::area) are extrapolated.
+    pub fn pixel_to_data(&self, point: Point) -> (f64, f64) {
+        let (x, y) = self.fraction(point);
+        (lerp(&self.x, x), lerp(&self.y, y))
+    }
+
+    /// Convert data coordinates to a position in the widget.
+    pub fn data_to_pixel(&self, (x, y): (f64, f64)) -> Point {
+        Point::new(
+            self.area.x0 + self.area.width() * unlerp(&self.x, x),
+            self.area.y1 - self.area.height() * unlerp(&self.y, y),
+        )
+    }
+
+    /// The position of `point` relative to [`area`](Self::area), with `(0.0, 0.0)` being
+    /// the lower left corner and `(1.0, 1.0)` the upper right one.
+    fn fraction(&self, point: Point) -> (f64, f64) {
+        (
+            (point.x - self.area.x0) / self.area.width(),
+            (self.area.y1 - point.y) / self.area.height(),
+        )
+    }
+}
+
+fn range_into_f64<T: Into<f64>>(range: Range<T>) -> Range<f64> {
+    range.start.into()..range.end.into()
+}
+
+fn lerp(range: &Range<f64>, t: f64) -> f64 {
+    range.start + t * (range.end - range.start)
+}
+
+fn unlerp(range: &Range<f64>, value: f64) -> f64 {
+    (value - range.start) / (range.end - range.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use masonry::kurbo::Point;
+    use plotters::coord::CoordTranslate;
+    use plotters::prelude::*;
+    use plotters_vello::VelloBackend;
+
+    use super::CoordMapping;
+
+    #[test]
+    fn matches_chart() {
+        let mut scene = masonry::vello::Scene::new();
+        let root = VelloBackend::new((200, 100), &mut scene).into_drawing_area();
+        let chart = ChartBuilder::on(&root)
+            .margin(5)
+            .x_label_area_size(20)
+            .y_label_area_size(30)
+            .build_cartesian_2d(-1f32..1f32, 0f32..10f32)
+            .unwrap();
+        let mapping = CoordMapping::from_chart(&chart);
+
+        for data in [(-1., 0.), (1., 10.), (0., 5.), (0.5, 2.)] {
+            let (x, y) = chart
+                .as_coord_spec()
+                .translate(&(data.0 as f32, data.1 as f32));
+            let pixel = mapping.data_to_pixel(data);
+            assert!((pixel.x - f64::from(x)).abs() <= 1., "{data:?}");
+            assert!((pixel.y - f64::from(y)).abs() <= 1., "{data:?}");
+        }
+
+        let (x, y) = mapping.pixel_to_data(mapping.data_to_pixel((0.25, 7.5)));
+        assert!((x - 0.25).abs() < 1e-9 && (y - 7.5).abs() < 1e-9);
+        assert_eq!(
+            mapping.pixel_to_data(Point::new(mapping.area().x0, mapping.area().y0)),
+            (-1., 10.)
+        );
+    }
+}
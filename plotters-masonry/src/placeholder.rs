@@ -0,0 +1,54 @@
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+
+use crate::MasonryBackend;
+
+/// The look of the placeholder a [`Plot`](crate::Plot) shows when its plot function fails.
+///
+/// See [`Plot::with_error_style`](crate::Plot::with_error_style).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorStyle {
+    /// The fill color of the plot area.
+    pub background: RGBAColor,
+    /// The color of the error message.
+    pub text: RGBAColor,
+    /// The font size of the error message.
+    pub font_size: f64,
+}
+
+impl Default for ErrorStyle {
+    fn default() -> Self {
+        Self {
+            background: RGBColor(255, 240, 240).to_rgba(),
+            text: RGBColor(176, 0, 32).to_rgba(),
+            font_size: 14.,
+        }
+    }
+}
+
+/// Fill `root` and show `message` in its center, one line per line of the message.
+pub(crate) fn draw(
+    root: &DrawingArea<MasonryBackend, Shift>,
+    style: &ErrorStyle,
+    message: &str,
+) -> Result<(), DrawingAreaErrorKind<plotters_vello::Error>> {
+    root.fill(&style.background)?;
+
+    let font = ("sans-serif", style.font_size)
+        .into_font()
+        .color(&style.text)
+        .pos(Pos::new(HPos::Center, VPos::Top));
+    let lines: Vec<&str> = message.lines().collect();
+    let line_height = root.estimate_text_size("Xg", &font)?.1 as i32;
+    let (width, height) = root.dim_in_pixel();
+    let top = height as i32 / 2 - line_height * lines.len() as i32 / 2;
+    for (i, line) in lines.iter().enumerate() {
+        root.draw(&Text::new(
+            *line,
+            (width as i32 / 2, top + i as i32 * line_height),
+            &font,
+        ))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use std::ops::Range;
+
+use masonry::kurbo::{Point, Rect};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::tooltip::Series;
+use crate::{CoordMapping, MasonryBackend, PointIndex};
+
+/// A rubber-band selection that is in progress.
+pub(crate) struct Selection {
+    pub(crate) start: Point,
+    pub(crate) current: Point,
+}
+
+impl Selection {
+    /// The selected rectangle in widget pixels, limited to `area`.
+    pub(crate) fn rect(&self, area: Rect) -> Rect {
+        let clamp = |point: Point| {
+            Point::new(
+                point.x.clamp(area.x0, area.x1),
+                point.y.clamp(area.y0, area.y1),
+            )
+        };
+        Rect::from_points(clamp(self.start), clamp(self.current))
+    }
+
+    /// The selected ranges in data coordinates.
+    pub(crate) fn data_ranges(&self, mapping: &CoordMapping) -> (Range<f64>, Range<f64>) {
+        let rect = self.rect(mapping.area());
+        let (x0, y0) = mapping.pixel_to_data(Point::new(rect.x0, rect.y1));
+        let (x1, y1) = mapping.pixel_to_data(Point::new(rect.x1, rect.y0));
+        (x0.min(x1)..x0.max(x1), y0.min(y1)..y0.max(y1))
+    }
+}
+
+/// The indices of all registered points inside the given ranges.
+pub(crate) fn points_in(series: &[Series], x: &Range<f64>, y: &Range<f64>) -> Vec<PointIndex> {
+    let contains = |range: &Range<f64>, value: f64| range.start <= value && value <= range.end;
+    series
+        .iter()
+        .enumerate()
+        .flat_map(|(series_index, series)| {
+            series
+                .points
+                .iter()
+                .enumerate()
+                .filter(|(_, (px, py))| contains(x, *px) && contains(y, *py))
+                .map(move |(index, _)| PointIndex {
+                    series: series_index,
+                    index,
+                })
+        })
+        .collect()
+}
+
+/// Draw the translucent selection rectangle.
+pub(crate) fn draw(
+    root: &DrawingArea<MasonryBackend, Shift>,
+    rect: Rect,
+) -> Result<(), DrawingAreaErrorKind<plotters_vello::Error>> {
+    let color = RGBColor(64, 128, 255);
+    let corners = [
+        (rect.x0.round() as i32, rect.y0.round() as i32),
+        (rect.x1.round() as i32, rect.y1.round() as i32),
+    ];
+    root.draw(&Rectangle::new(corners, color.mix(0.2).filled()))?;
+    root.draw(&Rectangle::new(corners, color.mix(0.8)))
+}
+
+#[cfg(test)]
+mod tests {
+    use masonry::kurbo::{Point, Rect};
+
+    use super::{Selection, points_in};
+    use crate::tooltip::Series;
+    use crate::{CoordMapping, PointIndex};
+
+    #[test]
+    fn selected_points() {
+        let mapping = CoordMapping::new(Rect::new(0., 0., 100., 100.), 0.0..10.0, 0.0..10.0);
+        // Dragging from the lower right to beyond the upper left corner of the chart
+        let selection = Selection {
+            start: Point::new(60., 80.),
+            current: Point::new(-20., 30.),
+        };
+        let (x, y) = selection.data_ranges(&mapping);
+        assert_eq!((x.clone(), y.clone()), (0.0..6.0, 2.0..7.0));
+
+        let series = [Series {
+            name: "a".into(),
+            points: vec![(1., 1.), (3., 5.), (6., 7.), (7., 5.)],
+        }];
+        assert_eq!(
+            points_in(&series, &x, &y),
+            [
+                PointIndex {
+                    series: 0,
+                    index: 1
+                },
+                PointIndex {
+                    series: 0,
+                    index: 2
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,87 @@
+use masonry::core::BoxConstraints;
+use masonry::kurbo::Size;
+
+/// The size of a plot in directions in which its container doesn't limit it and no preferred
+/// size was set.
+const DEFAULT_SIZE: Size = Size::new(400., 300.);
+
+/// How a [`Plot`](crate::Plot) chooses its size.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Sizing {
+    pub(crate) preferred: Option<Size>,
+    /// The width divided by the height.
+    pub(crate) aspect_ratio: Option<f64>,
+    pub(crate) min: Size,
+}
+
+impl Sizing {
+    /// The size of the plot within `bc`.
+    ///
+    /// The plot takes its preferred size, or all available space if it has none. An aspect
+    /// ratio shrinks this to fit the ratio, or sets the size in a direction in which the plot
+    /// isn't limited from the other direction.
+    pub(crate) fn layout(&self, bc: &BoxConstraints) -> Size {
+        let Size {
+            mut width,
+            mut height,
+        } = self.preferred.unwrap_or(bc.max());
+        if let Some(ratio) = self.aspect_ratio.filter(|ratio| *ratio > 0.) {
+            match (width.is_finite(), height.is_finite()) {
+                (true, true) => {
+                    width = width.min(height * ratio);
+                    height = width / ratio;
+                }
+                (true, false) => height = width / ratio,
+                (false, true) => width = height * ratio,
+                (false, false) => {
+                    width = DEFAULT_SIZE.width;
+                    height = width / ratio;
+                }
+            }
+        }
+        if !width.is_finite() {
+            width = DEFAULT_SIZE.width;
+        }
+        if !height.is_finite() {
+            height = DEFAULT_SIZE.height;
+        }
+        bc.constrain(Size::new(
+            width.max(self.min.width),
+            height.max(self.min.height),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use masonry::core::BoxConstraints;
+    use masonry::kurbo::Size;
+
+    use super::{DEFAULT_SIZE, Sizing};
+
+    #[test]
+    fn honors_constraints() {
+        let bounded = BoxConstraints::new(Size::ZERO, Size::new(800., 600.));
+        let unbounded_height = BoxConstraints::new(Size::ZERO, Size::new(800., f64::INFINITY));
+        let unbounded = BoxConstraints::new(Size::ZERO, Size::new(f64::INFINITY, f64::INFINITY));
+
+        let fill = Sizing::default();
+        assert_eq!(fill.layout(&bounded), Size::new(800., 600.));
+        assert_eq!(fill.layout(&unbounded_height), Size::new(800., 300.));
+        assert_eq!(fill.layout(&unbounded), DEFAULT_SIZE);
+
+        let wide = Sizing {
+            aspect_ratio: Some(2.),
+            ..Sizing::default()
+        };
+        assert_eq!(wide.layout(&bounded), Size::new(800., 400.));
+        assert_eq!(wide.layout(&unbounded_height), Size::new(800., 400.));
+
+        let preferred = Sizing {
+            preferred: Some(Size::new(1000., 200.)),
+            min: Size::new(0., 250.),
+            ..Sizing::default()
+        };
+        assert_eq!(preferred.layout(&bounded), Size::new(800., 250.));
+    }
+}
@@ -0,0 +1,167 @@
+use masonry::kurbo::Point;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+
+use crate::{CoordMapping, MasonryBackend, PointIndex};
+
+/// The look of the tooltips of a [`Plot`](crate::Plot) and when they are shown.
+///
+/// See [`Plot::with_tooltips`](crate::Plot::with_tooltips).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TooltipStyle {
+    /// The fill color of the tooltip box.
+    pub background: RGBAColor,
+    /// The color of the border of the tooltip box and of the marker on the hovered point.
+    pub border: RGBAColor,
+    /// The color of the text in the tooltip.
+    pub text: RGBAColor,
+    /// The font size of the text in the tooltip.
+    pub font_size: f64,
+    /// How close the pointer has to be to a point, in pixels, to show its tooltip.
+    pub max_distance: f64,
+}
+
+impl Default for TooltipStyle {
+    fn default() -> Self {
+        Self {
+            background: RGBColor(41, 41, 41).mix(0.9),
+            border: RGBColor(128, 128, 128).to_rgba(),
+            text: WHITE.to_rgba(),
+            font_size: 14.,
+            max_distance: 10.,
+        }
+    }
+}
+
+/// The distance between the hovered point and the tooltip, and between the tooltip's
+/// border and its text.
+const PADDING: i32 = 6;
+
+/// A data series registered with [`PlotContext::register_series`](crate::PlotContext::register_series).
+#[derive(Debug, Clone)]
+pub(crate) struct Series {
+    pub(crate) name: String,
+    pub(crate) points: Vec<(f64, f64)>,
+}
+
+/// Find the point closest to `position` that is at most `max_distance` pixels away.
+pub(crate) fn nearest_point(
+    series: &[Series],
+    mapping: &CoordMapping,
+    position: Point,
+    max_distance: f64,
+) -> Option<PointIndex> {
+    let mut nearest = None;
+    let mut nearest_distance = max_distance;
+    for (series_index, series) in series.iter().enumerate() {
+        for (index, &point) in series.points.iter().enumerate() {
+            let distance = mapping.data_to_pixel(point).distance(position);
+            if distance <= nearest_distance {
+                nearest = Some(PointIndex {
+                    series: series_index,
+                    index,
+                });
+                nearest_distance = distance;
+            }
+        }
+    }
+    nearest
+}
+
+/// Draw a marker on the point at `position` and a box with the series name and the
+/// coordinates of the point next to it.
+pub(crate) fn draw(
+    root: &DrawingArea<MasonryBackend, Shift>,
+    style: &TooltipStyle,
+    name: &str,
+    (x, y): (f64, f64),
+    position: Point,
+) -> Result<(), DrawingAreaErrorKind<plotters_vello::Error>> {
+    let font = ("sans-serif", style.font_size)
+        .into_font()
+        .color(&style.text);
+    let lines = [name.to_owned(), format!("x: {x:.3}"), format!("y: {y:.3}")];
+    let lines = if name.is_empty() { &lines[1..] } else { &lines };
+
+    let mut width = 0;
+    let mut line_height = 0;
+    for line in lines {
+        let (w, h) = root.estimate_text_size(line, &font)?;
+        width = width.max(w as i32);
+        line_height = line_height.max(h as i32);
+    }
+    let size = (
+        width + 2 * PADDING,
+        line_height * lines.len() as i32 + 2 * PADDING,
+    );
+
+    // Put the box below and right of the point, unless that would leave the widget
+    let point = (position.x.round() as i32, position.y.round() as i32);
+    let (root_width, root_height) = root.dim_in_pixel();
+    let mut origin = (point.0 + PADDING, point.1 + PADDING);
+    if origin.0 + size.0 > root_width as i32 {
+        origin.0 = point.0 - PADDING - size.0;
+    }
+    if origin.1 + size.1 > root_height as i32 {
+        origin.1 = point.1 - PADDING - size.1;
+    }
+    let corner = (origin.0 + size.0, origin.1 + size.1);
+
+    root.draw(&Circle::new(point, 4, style.border.stroke_width(2)))?;
+    root.draw(&Rectangle::new([origin, corner], style.background.filled()))?;
+    root.draw(&Rectangle::new([origin, corner], style.border))?;
+    for (i, line) in lines.iter().enumerate() {
+        root.draw(&Text::new(
+            line.as_str(),
+            (
+                origin.0 + PADDING,
+                origin.1 + PADDING + i as i32 * line_height,
+            ),
+            &font,
+        ))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use masonry::kurbo::{Point, Rect};
+
+    use super::{Series, nearest_point};
+    use crate::{CoordMapping, PointIndex};
+
+    #[test]
+    fn finds_nearest_point() {
+        let mapping = CoordMapping::new(Rect::new(0., 0., 100., 100.), 0.0..10.0, 0.0..10.0);
+        let series = [
+            Series {
+                name: "a".into(),
+                points: vec![(1., 1.), (5., 5.)],
+            },
+            Series {
+                name: "b".into(),
+                points: vec![(5., 6.)],
+            },
+        ];
+
+        // (5, 5) is at (50, 50) and (5, 6) at (50, 40)
+        assert_eq!(
+            nearest_point(&series, &mapping, Point::new(52., 47.), 10.),
+            Some(PointIndex {
+                series: 0,
+                index: 1
+            })
+        );
+        assert_eq!(
+            nearest_point(&series, &mapping, Point::new(50., 42.), 10.),
+            Some(PointIndex {
+                series: 1,
+                index: 0
+            })
+        );
+        assert_eq!(
+            nearest_point(&series, &mapping, Point::new(80., 80.), 10.),
+            None
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use std::ops::Range;
+
+/// The part of the data space that is visible in a plot.
+///
+/// When pan and zoom are enabled with [`Plot::with_pan_zoom`](crate::Plot::with_pan_zoom),
+/// the widget keeps track of the current viewport and passes it to the plot function
+/// through [`PlotContext::viewport`](crate::PlotContext::viewport). The plot function
+/// should then use these ranges as the coordinate ranges of its chart.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Viewport {
+    /// The visible range of the x axis, from left to right.
+    pub x: Range<f64>,
+    /// The visible range of the y axis, from bottom to top.
+    pub y: Range<f64>,
+}
+
+impl Viewport {
+    /// Create a viewport showing `x` horizontally and `y` vertically.
+    pub fn new(x: Range<f64>, y: Range<f64>) -> Self {
+        Self { x, y }
+    }
+
+    /// Zoom in by `factor` around an anchor point, which stays fixed.
+    ///
+    /// The anchor is given as a fraction of the viewport, with `(0.0, 0.0)` being the lower
+    /// left corner and `(1.0, 1.0)` the upper right one. A `factor` smaller than 1 zooms out.
+    pub fn zoomed(&self, factor: f64, (anchor_x, anchor_y): (f64, f64)) -> Self {
+        Self {
+            x: zoom_range(&self.x, factor, anchor_x),
+            y: zoom_range(&self.y, factor, anchor_y),
+        }
+    }
+
+    /// Move the viewport by a fraction of its size.
+    ///
+    /// Moving by `(1.0, 0.0)` shows the data that is currently right of the viewport.
+    pub fn panned(&self, (dx, dy): (f64, f64)) -> Self {
+        Self {
+            x: pan_range(&self.x, dx),
+            y: pan_range(&self.y, dy),
+        }
+    }
+
+    /// Whether all bounds are finite and both ranges are not empty.
+    pub(crate) fn is_valid(&self) -> bool {
+        [&self.x, &self.y].into_iter().all(|range| {
+            range.start.is_finite() && range.end.is_finite() && range.start != range.end
+        })
+    }
+}
+
+fn zoom_range(range: &Range<f64>, factor: f64, anchor: f64) -> Range<f64> {
+    let span = range.end - range.start;
+    let center = range.start + anchor * span;
+    let span = span / factor;
+    (center - anchor * span)..(center + (1. - anchor) * span)
+}
+
+fn pan_range(range: &Range<f64>, delta: f64) -> Range<f64> {
+    let offset = delta * (range.end - range.start);
+    (range.start + offset)..(range.end + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Viewport;
+
+    #[test]
+    fn zoom_and_pan() {
+        let viewport = Viewport::new(0.0..10.0, -1.0..1.0);
+
+        assert_eq!(
+            viewport.zoomed(2.0, (0.5, 0.5)),
+            Viewport::new(2.5..7.5, -0.5..0.5)
+        );
+        assert_eq!(
+            viewport.zoomed(2.0, (0.0, 1.0)),
+            Viewport::new(0.0..5.0, 0.0..1.0)
+        );
+        assert_eq!(
+            viewport.zoomed(0.5, (0.5, 0.5)).zoomed(2.0, (0.5, 0.5)),
+            viewport
+        );
+        assert_eq!(
+            viewport.panned((0.5, -0.25)),
+            Viewport::new(5.0..15.0, -1.5..0.5)
+        );
+        assert!(!viewport.zoomed(f64::INFINITY, (0.5, 0.5)).is_valid());
+    }
+}
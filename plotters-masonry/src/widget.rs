@@ -1,13 +1,206 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::ops::Range;
+
 use accesskit::{Node, Role};
-use masonry::core::{ArcStr, NoAction, Widget, WidgetMut};
-use masonry::kurbo;
+use masonry::core::{
+    ArcStr, BoxConstraints, EventCtx, LayoutCtx, PaintCtx, PointerButton, PointerButtonEvent,
+    PointerEvent, PointerScrollEvent, PointerUpdate, PropertiesMut, ScrollDelta, Update, UpdateCtx,
+    Widget, WidgetId, WidgetMut,
+};
+use masonry::kurbo::{Affine, Point, Rect, Size};
+use masonry::parley::FontContext;
+use masonry::vello::Scene;
 use plotters::coord::Shift;
+use plotters::coord::ranged1d::Ranged;
 use plotters::prelude::*;
 use smallvec::SmallVec;
 
-use crate::MasonryBackend;
+use crate::selection::{self, Selection};
+use crate::sizing::Sizing;
+use crate::tooltip::{self, Series};
+use crate::{
+    CoordMapping, CrosshairStyle, ErrorStyle, HoveredPoint, MasonryBackend, PlotAction, PlotGroup,
+    PointIndex, StrokeStyles, TooltipStyle, Viewport, group,
+};
+use crate::{crosshair, placeholder};
+
+/// The function drawing the plot of a [`Plot`] widget.
+pub type PlotFn<Data> = Box<
+    dyn Fn(
+        &PlotContext,
+        &mut Data,
+        &DrawingArea<MasonryBackend, Shift>,
+    ) -> Result<(), Box<dyn Error>>,
+>;
+
+/// How much scrolling by one line zooms in.
+const ZOOM_PER_LINE: f64 = 1.2;
+
+/// The number of pixels of a pixel based scroll delta that count as one line.
+const PIXELS_PER_LINE: f64 = 120.;
+
+/// How far the pointer may move between pressing and releasing a button for it to still
+/// count as a click, in pixels.
+const CLICK_DISTANCE: f64 = 4.;
+
+/// Information about the widget passed to the plot function of a [`Plot`].
+#[derive(Debug)]
+pub struct PlotContext {
+    size: (u32, u32),
+    scale_factor: f64,
+    viewport: Option<Viewport>,
+    stroke_styles: StrokeStyles,
+    coord_mapping: RefCell<Option<CoordMapping>>,
+    series: RefCell<Vec<Series>>,
+}
+
+impl PlotContext {
+    /// The width and height of the plotting area in device pixels.
+    ///
+    /// This is the logical size of the widget multiplied by the
+    /// [`scale_factor`](Self::scale_factor), like all pixel sizes the plot function passes to
+    /// plotters, e.g. the sizes of label areas.
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// The number of device pixels per logical pixel.
+    ///
+    /// The plot is drawn in device pixels, so sizes like margins or line widths that should
+    /// look the same on all displays have to be multiplied by this. Fonts can be scaled
+    /// automatically with [`Plot::with_logical_font_size`].
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// The part of the data space the user panned and zoomed to.
+    ///
+    /// This is `None` unless pan and zoom were enabled with [`Plot::with_pan_zoom`].
+    pub fn viewport(&self) -> Option<&Viewport> {
+        self.viewport.as_ref()
+    }
+
+    /// The styles the plot's outlines are stroked with.
+    ///
+    /// Change them to draw dashed lines or to use different joins or caps, e.g. for a dashed
+    /// threshold line:
+    ///
+    /// ```rust
+    /// # use plotters_masonry::{Plot, StrokedPrimitive, VelloStrokeStyle};
+    /// # use plotters::prelude::*;
+    /// Plot::new(
+    ///     (),
+    ///     |ctx, _data, root| {
+    ///         let mut chart = ChartBuilder::on(&root).build_cartesian_2d(0.0..10.0, 0.0..1.0)?;
+    ///
+    ///         let styles = ctx.stroke_styles();
+    ///         styles.set(StrokedPrimitive::Path, VelloStrokeStyle::new().with_dashes(0., [6., 4.]));
+    ///         chart.draw_series(LineSeries::new([(0.0, 0.8), (10.0, 0.8)], &RED))?;
+    ///         styles.reset(StrokedPrimitive::Path);
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// );
+    /// ```
+    pub fn stroke_styles(&self) -> &StrokeStyles {
+        &self.stroke_styles
+    }
+
+    /// Let the widget know how the data coordinates of `chart` map to pixels.
+    ///
+    /// Afterwards [`Plot::coord_mapping`] can be used to convert between positions in the
+    /// widget and data coordinates, e.g. to find out what the pointer is pointing at. Pan
+    /// and zoom also use the registered chart to zoom around the data under the pointer.
+    /// If the plot function draws several charts only the last registered one is kept.
+    ///
+    /// ```rust
+    /// # use plotters_masonry::Plot;
+    /// # use plotters::prelude::*;
+    /// Plot::new(
+    ///     (),
+    ///     |ctx, _data, root| {
+    ///         let chart = ChartBuilder::on(&root)
+    ///             .x_label_area_size(30)
+    ///             .y_label_area_size(30)
+    ///             .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)?;
+    ///         ctx.register_chart(&chart);
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// );
+    /// ```
+    pub fn register_chart<X, Y>(
+        &self,
+        chart: &ChartContext<'_, MasonryBackend<'_>, Cartesian2d<X, Y>>,
+    ) where
+        X: Ranged,
+        Y: Ranged,
+        X::ValueType: Into<f64>,
+        Y::ValueType: Into<f64>,
+    {
+        *self.coord_mapping.borrow_mut() = Some(CoordMapping::from_chart(chart));
+    }
+
+    /// Let the widget know about the points of a data series drawn by the plot function.
+    ///
+    /// The points are given in data coordinates and are mapped to pixels using the chart
+    /// registered with [`register_chart`](Self::register_chart). They are used to show
+    /// tooltips, see [`Plot::with_tooltips`].
+    ///
+    /// ```rust
+    /// # use plotters_masonry::Plot;
+    /// # use plotters::prelude::*;
+    /// Plot::new(
+    ///     vec![(0.0, 1.0), (1.0, 3.0), (2.0, 2.0)],
+    ///     |ctx, data, root| {
+    ///         let mut chart = ChartBuilder::on(&root)
+    ///             .build_cartesian_2d(0.0..2.0, 0.0..3.0)?;
+    ///         ctx.register_chart(&chart);
+    ///
+    ///         chart.draw_series(LineSeries::new(data.clone(), &RED))?;
+    ///         ctx.register_series("measurements", data.iter().copied());
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// );
+    /// ```
+    pub fn register_series<X: Into<f64>, Y: Into<f64>>(
+        &self,
+        name: impl Into<String>,
+        points: impl IntoIterator<Item = (X, Y)>,
+    ) {
+        self.series.borrow_mut().push(Series {
+            name: name.into(),
+            points: points
+                .into_iter()
+                .map(|(x, y)| (x.into(), y.into()))
+                .collect(),
+        });
+    }
+}
+
+/// The state of pan and zoom of a [`Plot`].
+struct PanZoom {
+    home: Viewport,
+    current: Viewport,
+    drag: Option<Drag>,
+}
+
+/// The membership of a [`Plot`] in a [`PlotGroup`].
+struct Link {
+    group: PlotGroup,
+    /// The id of the widget, once it was added to the widget tree.
+    id: Option<WidgetId>,
+    /// The generation of the group state the plot was last painted with.
+    generation: u64,
+}
 
-pub type PlotFn<Data> = fn((u32, u32), &mut Data, &DrawingArea<MasonryBackend, Shift>);
+/// A drag of the plot that is in progress.
+struct Drag {
+    start: Point,
+    viewport: Viewport,
+}
 
 /// The type of a plot widget.
 ///
@@ -16,86 +209,1059 @@ pub type PlotFn<Data> = fn((u32, u32), &mut Data, &DrawingArea<MasonryBackend, S
 /// This implements [`masonry::core::Widget`] so it can be used like
 /// any other widget type.
 /// ```rust
-/// # use druid::{Widget, WindowDesc, AppLauncher};
-/// # use plotters_druid::Plot;
-/// fn build_plot_widget() -> impl Widget<()> {
+/// # use masonry::core::{NewWidget, Widget};
+/// # use plotters_masonry::Plot;
+/// fn build_plot_widget() -> NewWidget<Plot<()>> {
 ///     // ... construct and return widget using Plot::new()
-///     # Plot::new(|_, _, _|{})
+///     # NewWidget::new(Plot::new((), |_, _, _| Ok(()), "plot"))
 /// }
-///
-/// # fn main() {
-/// let main_window = WindowDesc::new(build_plot_widget());
-/// # }
 /// ```
 pub struct Plot<Data> {
     alt_text: ArcStr,
     data: Data,
     plot: PlotFn<Data>,
+    pan_zoom: Option<PanZoom>,
+    coord_mapping: Option<CoordMapping>,
+    series: Vec<Series>,
+    tooltip_style: Option<TooltipStyle>,
+    hovered: Option<PointIndex>,
+    press: Option<(Point, PointerButton)>,
+    link: Option<Link>,
+    selection_enabled: bool,
+    selection: Option<Selection>,
+    crosshair_style: Option<CrosshairStyle>,
+    /// The position of the pointer while it is over the widget.
+    pointer: Option<Point>,
+    logical_font_size: bool,
+    batching: bool,
+    sizing: Sizing,
+    error_style: ErrorStyle,
+    /// The error returned by the plot function the last time it ran.
+    error: Option<String>,
+    /// A new error returned by the plot function while painting, which can't submit actions.
+    paint_error: Option<String>,
+    /// Increased whenever an input of the plot function changes.
+    plot_generation: u64,
+    /// The output of the plot function, drawn below the overlays.
+    plot_scene: Scene,
+    /// The generation, size and scale factor `plot_scene` was drawn with.
+    plot_key: Option<(u64, (u32, u32), f64)>,
 }
 
 impl<Data: 'static> Plot<Data> {
     /// Create a plot widget
     ///
     /// This takes a function that should draw the plot using the normal plotters API.
-    /// The function has access to the width and height of the plotting area and to a
-    /// plotters [`DrawingArea`].
+    /// The function has access to a [`PlotContext`] describing the widget, to the
+    /// widget's data and to a plotters [`DrawingArea`]. It can be any closure, so it may
+    /// capture configuration or shared state it needs for drawing.
+    ///
+    /// Errors of the plotters API can be returned with `?`. The widget then shows the error
+    /// message instead of the plot and submits a [`PlotAction::Error`], see
+    /// [`Plot::with_error_style`].
+    ///
+    /// The output of the function is kept and painted again until the data, the viewport or
+    /// the size of the widget change, so e.g. showing a tooltip doesn't redraw the plot. If
+    /// the function captures state that changes, use [`Plot::invalidate`] to redraw it.
     ///
     /// ```rust
     /// # use plotters_masonry::Plot;
     /// # use plotters::prelude::*;
-    /// Plot::new(|(width, height), root| {
-    ///     root.fill(&WHITE).unwrap();
-    ///     let mut chart = ChartBuilder::on(&root)
-    ///         .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)
-    ///         .unwrap();
-    ///
-    ///     // see the plotters documentation on how to use `chart`
-    /// });
+    /// let color = RED;
+    /// Plot::new(
+    ///     (),
+    ///     move |ctx, _data, root| {
+    ///         let (width, height) = ctx.size();
+    ///         root.fill(&color)?;
+    ///         let mut chart = ChartBuilder::on(&root)
+    ///             .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)?;
+    ///
+    ///         // see the plotters documentation on how to use `chart`
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// );
     /// ```
-    pub fn new(data: Data, plot: PlotFn<Data>, alt_text: impl Into<ArcStr>) -> Self {
+    pub fn new(
+        data: Data,
+        plot: impl Fn(
+            &PlotContext,
+            &mut Data,
+            &DrawingArea<MasonryBackend, Shift>,
+        ) -> Result<(), Box<dyn Error>>
+        + 'static,
+        alt_text: impl Into<ArcStr>,
+    ) -> Self {
         Self {
             alt_text: alt_text.into(),
             data,
-            plot,
+            plot: Box::new(plot),
+            pan_zoom: None,
+            coord_mapping: None,
+            series: Vec::new(),
+            tooltip_style: None,
+            hovered: None,
+            press: None,
+            link: None,
+            selection_enabled: false,
+            selection: None,
+            crosshair_style: None,
+            pointer: None,
+            logical_font_size: false,
+            batching: false,
+            sizing: Sizing::default(),
+            error_style: ErrorStyle::default(),
+            error: None,
+            paint_error: None,
+            plot_generation: 0,
+            plot_scene: Scene::new(),
+            plot_key: None,
         }
     }
 
+    /// Enable panning and zooming, starting out at the `home` viewport.
+    ///
+    /// Scrolling zooms around the pointer, dragging with the primary button pans and a
+    /// double click goes back to `home`. The plot function gets the current viewport from
+    /// [`PlotContext::viewport`] and should use it as the coordinate ranges of its chart.
+    ///
+    /// ```rust
+    /// # use plotters_masonry::{Plot, Viewport};
+    /// # use plotters::prelude::*;
+    /// Plot::new(
+    ///     (),
+    ///     |ctx, _data, root| {
+    ///         let viewport = ctx.viewport().unwrap();
+    ///         let mut chart = ChartBuilder::on(&root)
+    ///             .build_cartesian_2d(viewport.x.clone(), viewport.y.clone())?;
+    ///
+    ///         // see the plotters documentation on how to use `chart`
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// )
+    /// .with_pan_zoom(Viewport::new(0.0..10.0, -1.0..1.0));
+    /// ```
+    pub fn with_pan_zoom(mut self, home: Viewport) -> Self {
+        self.pan_zoom = Some(PanZoom::new(home));
+        self
+    }
+
+    /// Show a tooltip for the registered point closest to the pointer.
+    ///
+    /// The tooltip shows the name of the series and the coordinates of the point. Points
+    /// are registered with [`PlotContext::register_series`], which requires the chart to
+    /// be registered with [`PlotContext::register_chart`] as well.
+    pub fn with_tooltips(mut self, style: TooltipStyle) -> Self {
+        self.tooltip_style = Some(style);
+        self
+    }
+
+    /// Show `viewport` instead of the home viewport.
+    ///
+    /// If pan and zoom haven't been enabled with [`with_pan_zoom`](Self::with_pan_zoom) yet,
+    /// this enables them with `viewport` as the home viewport.
+    pub fn with_viewport(mut self, viewport: Viewport) -> Self {
+        match &mut self.pan_zoom {
+            Some(pan_zoom) => pan_zoom.current = viewport,
+            None => self.pan_zoom = Some(PanZoom::new(viewport)),
+        }
+        self
+    }
+
+    /// Add the plot to `group`, linking its x axis and hover position with the other plots
+    /// of the group.
+    ///
+    /// See [`PlotGroup`] for details.
+    ///
+    /// ```rust
+    /// # use plotters_masonry::{Plot, PlotGroup, Viewport};
+    /// # use plotters::prelude::*;
+    /// fn time_series(group: &PlotGroup, name: &str) -> Plot<Vec<(f64, f64)>> {
+    ///     Plot::new(
+    ///         Vec::new(),
+    ///         |ctx, data, root| {
+    ///             let viewport = ctx.viewport().unwrap();
+    ///             let chart = ChartBuilder::on(&root)
+    ///                 .build_cartesian_2d(viewport.x.clone(), viewport.y.clone())?;
+    ///             ctx.register_chart(&chart);
+    ///             Ok(())
+    ///         },
+    ///         name.to_owned(),
+    ///     )
+    ///     .with_pan_zoom(Viewport::new(0.0..60.0, 0.0..1.0))
+    ///     .with_group(group.clone())
+    /// }
+    ///
+    /// let group = PlotGroup::new();
+    /// let plots = [time_series(&group, "cpu"), time_series(&group, "memory")];
+    /// ```
+    pub fn with_group(mut self, group: PlotGroup) -> Self {
+        self.link = Some(Link {
+            group,
+            id: None,
+            generation: 0,
+        });
+        self
+    }
+
+    /// Let the user select a rectangular region of the plot by dragging with shift held.
+    ///
+    /// While dragging, a translucent rectangle is drawn over the plot. On release the plot
+    /// submits a [`PlotAction::RangeSelected`] with the selected data ranges and the
+    /// registered points inside them. This requires the plot function to register its chart
+    /// with [`PlotContext::register_chart`].
+    pub fn with_selection(mut self) -> Self {
+        self.selection_enabled = true;
+        self
+    }
+
+    /// Show lines through the pointer across the chart, with the data coordinates of the
+    /// pointer in labels next to the axes.
+    ///
+    /// The crosshair is drawn on top of the plot, moving it doesn't run the plot function
+    /// again. This requires the plot function to register its chart with
+    /// [`PlotContext::register_chart`].
+    pub fn with_crosshair(mut self, style: CrosshairStyle) -> Self {
+        self.crosshair_style = Some(style);
+        self
+    }
+
+    /// Scale the fonts of the plot by the scale factor of the window.
+    ///
+    /// The plot is drawn in device pixels, so on a display with a scale factor of 2 a font
+    /// size of 20 is only as large as a font size of 10 on other displays. With this a font
+    /// size of 20 is as large as 20 logical pixels on all displays.
+    pub fn with_logical_font_size(mut self) -> Self {
+        self.logical_font_size = true;
+        self
+    }
+
+    /// Draw runs of primitives with the same style as a single path.
+    ///
+    /// This makes plots with thousands of points or grid lines faster to draw, see
+    /// [`VelloBackend::with_batching`](plotters_vello::VelloBackend::with_batching). Overlapping
+    /// translucent primitives of the same style no longer blend with each other. The widget
+    /// presents the drawing area after the plot function returns, so the plot function doesn't
+    /// need to.
+    pub fn with_batching(mut self) -> Self {
+        self.batching = true;
+        self
+    }
+
+    /// Ask for `size` instead of all the space the container offers.
+    ///
+    /// Without a preferred size the plot fills the available space, falling back to a default
+    /// size in directions in which the container doesn't limit it, e.g. in a scroll view.
+    pub fn with_preferred_size(mut self, size: Size) -> Self {
+        self.sizing.preferred = Some(size);
+        self
+    }
+
+    /// Keep the width of the plot at `ratio` times its height.
+    ///
+    /// The plot takes the largest size with this ratio that fits into its preferred size or
+    /// the available space. If its container limits only the width or only the height, the
+    /// other one follows from the ratio. The constraints of the container take precedence
+    /// over the ratio.
+    pub fn with_aspect_ratio(mut self, ratio: f64) -> Self {
+        self.sizing.aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Don't shrink the plot below `size`, unless the container requires it.
+    pub fn with_min_size(mut self, size: Size) -> Self {
+        self.sizing.min = size;
+        self
+    }
+
+    /// Show errors of the plot function in the given style.
+    ///
+    /// When the plot function returns an error, the plot shows the error message instead
+    /// and submits a [`PlotAction::Error`].
+    pub fn with_error_style(mut self, style: ErrorStyle) -> Self {
+        self.error_style = style;
+        self
+    }
+
+    /// The viewport currently shown, if pan and zoom are enabled.
+    pub fn viewport(&self) -> Option<&Viewport> {
+        self.pan_zoom.as_ref().map(|pan_zoom| &pan_zoom.current)
+    }
+
+    /// The error returned by the plot function the last time it ran, if any.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// The coordinate mapping registered by the plot function the last time the plot was
+    /// drawn.
+    ///
+    /// See [`PlotContext::register_chart`].
+    pub fn coord_mapping(&self) -> Option<&CoordMapping> {
+        self.coord_mapping.as_ref()
+    }
+
     pub fn set_data(this: &mut WidgetMut<'_, Self>, new_data: Data) {
         this.widget.data = new_data;
+        this.widget.hovered = None;
+        Self::redraw(this);
+    }
+
+    pub fn set_plot(
+        this: &mut WidgetMut<'_, Self>,
+        new_plot: impl Fn(
+            &PlotContext,
+            &mut Data,
+            &DrawingArea<MasonryBackend, Shift>,
+        ) -> Result<(), Box<dyn Error>>
+        + 'static,
+    ) {
+        this.widget.plot = Box::new(new_plot);
+        Self::redraw(this);
+    }
+
+    /// Run the plot function again.
+    ///
+    /// The output of the plot function is reused until the data, the plot function, the
+    /// viewport or the size of the widget change. Call this if the plot depends on anything
+    /// else, e.g. state captured by the plot function.
+    pub fn invalidate(this: &mut WidgetMut<'_, Self>) {
+        Self::redraw(this);
+    }
+
+    /// Enable or disable scaling fonts by the scale factor of the window.
+    ///
+    /// See [`Plot::with_logical_font_size`].
+    pub fn set_logical_font_size(this: &mut WidgetMut<'_, Self>, logical_font_size: bool) {
+        this.widget.logical_font_size = logical_font_size;
+        Self::redraw(this);
+    }
+
+    /// Enable or disable drawing runs of primitives with the same style as a single path.
+    ///
+    /// See [`Plot::with_batching`].
+    pub fn set_batching(this: &mut WidgetMut<'_, Self>, batching: bool) {
+        this.widget.batching = batching;
+        Self::redraw(this);
+    }
+
+    /// Show errors of the plot function in the given style.
+    ///
+    /// See [`Plot::with_error_style`].
+    pub fn set_error_style(this: &mut WidgetMut<'_, Self>, style: ErrorStyle) {
+        this.widget.error_style = style;
+        this.ctx.request_paint_only();
+    }
+
+    /// Set the preferred size of the plot, or let it fill the available space if `size` is
+    /// `None`.
+    ///
+    /// See [`Plot::with_preferred_size`].
+    pub fn set_preferred_size(this: &mut WidgetMut<'_, Self>, size: Option<Size>) {
+        this.widget.sizing.preferred = size;
+        this.ctx.request_layout();
+    }
+
+    /// Keep the width of the plot at `ratio` times its height, or don't keep any aspect
+    /// ratio if `ratio` is `None`.
+    ///
+    /// See [`Plot::with_aspect_ratio`].
+    pub fn set_aspect_ratio(this: &mut WidgetMut<'_, Self>, ratio: Option<f64>) {
+        this.widget.sizing.aspect_ratio = ratio;
+        this.ctx.request_layout();
+    }
+
+    /// Set the minimum size of the plot.
+    ///
+    /// See [`Plot::with_min_size`].
+    pub fn set_min_size(this: &mut WidgetMut<'_, Self>, size: Size) {
+        this.widget.sizing.min = size;
+        this.ctx.request_layout();
+    }
+
+    /// Enable panning and zooming starting out at `home`, or disable it if `home` is `None`.
+    ///
+    /// See [`Plot::with_pan_zoom`]. The current viewport is reset to `home`.
+    pub fn set_pan_zoom(this: &mut WidgetMut<'_, Self>, home: Option<Viewport>) {
+        this.widget.pan_zoom = home.map(PanZoom::new);
+        Self::redraw(this);
+    }
+
+    /// Show `viewport`, e.g. to sync the plot with other parts of the app.
+    ///
+    /// This cancels an ongoing drag and doesn't submit a [`PlotAction::ViewportChanged`].
+    /// See [`Plot::with_viewport`].
+    pub fn set_viewport(this: &mut WidgetMut<'_, Self>, viewport: Viewport) {
+        if this.widget.viewport() == Some(&viewport) {
+            return;
+        }
+        match &mut this.widget.pan_zoom {
+            Some(pan_zoom) => {
+                pan_zoom.current = viewport;
+                pan_zoom.drag = None;
+            }
+            None => this.widget.pan_zoom = Some(PanZoom::new(viewport)),
+        }
+        this.widget.hovered = None;
+        if let Some(link) = &this.widget.link
+            && let Some(viewport) = this.widget.viewport()
+        {
+            link.set_x(viewport.x.clone());
+        }
+        Self::redraw(this);
+    }
+
+    /// Add the plot to `group`, or remove it from its group if `group` is `None`.
+    ///
+    /// See [`Plot::with_group`].
+    pub fn set_group(this: &mut WidgetMut<'_, Self>, group: Option<PlotGroup>) {
+        let id = this.ctx.widget_id();
+        if let Some(link) = this.widget.link.take() {
+            link.group.leave(id);
+        }
+        if let Some(group) = group {
+            this.widget.join(group, id);
+        }
+        Self::redraw(this);
+    }
+
+    /// Redraw the plot if the state shared with its [`PlotGroup`] changed since it was
+    /// last painted.
+    pub fn sync_group(this: &mut WidgetMut<'_, Self>) {
+        let Some(link) = &this.widget.link else {
+            return;
+        };
+        if link.group.lock().generation == link.generation {
+            return;
+        }
+        if this.widget.sync_group_x() {
+            // Report the errors of drawing the new x range, see `Plot::redraw`
+            this.ctx.request_anim_frame();
+        }
+        this.ctx.request_paint_only();
+    }
+
+    /// Run the plot function again on the next paint.
+    ///
+    /// Painting can't submit actions and animation frames run before painting, so the error
+    /// of the plot function is submitted on the animation frame after it was painted, see
+    /// `on_anim_frame`.
+    fn redraw(this: &mut WidgetMut<'_, Self>) {
+        this.widget.plot_generation += 1;
         this.ctx.request_paint_only();
+        this.ctx.request_anim_frame();
+    }
+
+    /// Take over the x range shared with the group, returning whether it changed.
+    fn sync_group_x(&mut self) -> bool {
+        if let Some(link) = &self.link
+            && let (Some(pan_zoom), Some(x)) = (&mut self.pan_zoom, &link.group.lock().x)
+            && pan_zoom.current.x != *x
+        {
+            pan_zoom.current.x = x.clone();
+            self.plot_generation += 1;
+            return true;
+        }
+        false
+    }
+
+    fn join(&mut self, group: PlotGroup, id: WidgetId) {
+        group.join(id);
+        if let Some(pan_zoom) = &self.pan_zoom {
+            let mut state = group.lock();
+            if state.x.is_none() {
+                state.set_x(pan_zoom.current.x.clone());
+            }
+        }
+        self.link = Some(Link {
+            group,
+            id: Some(id),
+            generation: 0,
+        });
+    }
+
+    /// Share the position of the pointer with the group, returning whether it changed.
+    fn update_group_hover(&mut self, position: Option<Point>) -> bool {
+        let Some(link) = &self.link else {
+            return false;
+        };
+        let hover_x = match (&self.coord_mapping, position) {
+            (Some(mapping), Some(position))
+                if (mapping.area().x0..=mapping.area().x1).contains(&position.x) =>
+            {
+                Some(mapping.pixel_to_data(position).0)
+            }
+            _ => None,
+        };
+        let changed = link.group.lock().set_hover_x(hover_x);
+        if changed {
+            link.wake_others();
+        }
+        changed
+    }
+
+    /// Show tooltips in the given style, or disable them if `style` is `None`.
+    ///
+    /// See [`Plot::with_tooltips`].
+    pub fn set_tooltips(this: &mut WidgetMut<'_, Self>, style: Option<TooltipStyle>) {
+        this.widget.tooltip_style = style;
+        this.ctx.request_paint_only();
+    }
+
+    /// Enable or disable selecting a region of the plot.
+    ///
+    /// See [`Plot::with_selection`]. Disabling it cancels an ongoing selection.
+    pub fn set_selection(this: &mut WidgetMut<'_, Self>, enabled: bool) {
+        this.widget.selection_enabled = enabled;
+        if !enabled && this.widget.selection.take().is_some() {
+            this.ctx.request_paint_only();
+        }
     }
 
-    pub fn set_plot(this: &mut WidgetMut<'_, Self>, new_plot: PlotFn<Data>) {
-        this.widget.plot = new_plot;
+    /// Show a crosshair in the given style, or disable it if `style` is `None`.
+    ///
+    /// See [`Plot::with_crosshair`].
+    pub fn set_crosshair(this: &mut WidgetMut<'_, Self>, style: Option<CrosshairStyle>) {
+        this.widget.crosshair_style = style;
         this.ctx.request_paint_only();
     }
+
+    /// Remember the position of the pointer, repainting the crosshair if it is shown.
+    fn update_pointer(&mut self, position: Option<Point>) -> bool {
+        let changed = self.pointer != position;
+        self.pointer = position;
+        changed && self.crosshair_style.is_some()
+    }
+
+    /// Update the hovered point, returning whether it changed.
+    fn update_hovered(&mut self, position: Option<Point>) -> bool {
+        let max_distance = match &self.tooltip_style {
+            Some(style) => style.max_distance,
+            None => TooltipStyle::default().max_distance,
+        };
+        let hovered = match (&self.coord_mapping, position) {
+            (Some(mapping), Some(position)) => {
+                tooltip::nearest_point(&self.series, mapping, position, max_distance)
+            }
+            _ => None,
+        };
+        let changed = hovered != self.hovered;
+        self.hovered = hovered;
+        changed
+    }
+
+    /// The registered point closest to the pointer, if it is close enough.
+    fn hovered_point(&self) -> Option<HoveredPoint> {
+        let point = self.hovered?;
+        let series = self.series.get(point.series)?;
+        let &(data_x, data_y) = series.points.get(point.index)?;
+        Some(HoveredPoint {
+            point,
+            series_name: series.name.clone(),
+            data_x,
+            data_y,
+        })
+    }
+
+    /// Handle a pointer event as part of a selection, returning whether it was consumed.
+    fn handle_selection(&mut self, ctx: &mut EventCtx<'_>, event: &PointerEvent) -> bool {
+        if !self.selection_enabled {
+            return false;
+        }
+        match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            }) if state.modifiers.shift() && self.coord_mapping.is_some() => {
+                let start = ctx.local_position(state.position);
+                ctx.capture_pointer();
+                self.selection = Some(Selection {
+                    start,
+                    current: start,
+                });
+                true
+            }
+            PointerEvent::Move(PointerUpdate { current, .. }) => {
+                let Some(selection) = &mut self.selection else {
+                    return false;
+                };
+                selection.current = ctx.local_position(current.position);
+                ctx.request_paint_only();
+                true
+            }
+            PointerEvent::Up(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            }) => {
+                let Some(mut selection) = self.selection.take() else {
+                    return false;
+                };
+                selection.current = ctx.local_position(state.position);
+                ctx.request_paint_only();
+                if let Some(mapping) = &self.coord_mapping {
+                    let rect = selection.rect(mapping.area());
+                    if rect.width() > 0. && rect.height() > 0. {
+                        let (x, y) = selection.data_ranges(mapping);
+                        let points = selection::points_in(&self.series, &x, &y);
+                        ctx.submit_action::<PlotAction>(PlotAction::RangeSelected { x, y, points });
+                    }
+                }
+                true
+            }
+            PointerEvent::Cancel(_) => {
+                if self.selection.take().is_none() {
+                    return false;
+                }
+                ctx.request_paint_only();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Run the plot function if its inputs changed since it last ran, returning the error
+    /// if it is a new one.
+    ///
+    /// Overlays like the tooltip change far more often than the plot itself, so the output
+    /// of the plot function is kept in `plot_scene`. The plot is drawn in device pixels so
+    /// lines and text snap to the pixels of the display, Masonry scales the whole scene by
+    /// the scale factor again.
+    fn draw_plot(
+        &mut self,
+        font_ctx: &mut FontContext,
+        size: Size,
+        scale_factor: f64,
+    ) -> Option<String> {
+        let device_size = (
+            (size.width * scale_factor) as u32,
+            (size.height * scale_factor) as u32,
+        );
+        let key = (self.plot_generation, device_size, scale_factor);
+        if self.plot_key == Some(key) {
+            return None;
+        }
+        self.plot_key = Some(key);
+
+        let plot_ctx = PlotContext {
+            size: device_size,
+            scale_factor,
+            viewport: self
+                .pan_zoom
+                .as_ref()
+                .map(|pan_zoom| pan_zoom.current.clone()),
+            stroke_styles: StrokeStyles::new(),
+            coord_mapping: RefCell::new(None),
+            series: RefCell::new(Vec::new()),
+        };
+        self.plot_scene.reset();
+        let font_scale = if self.logical_font_size {
+            scale_factor
+        } else {
+            1.
+        };
+        let root = MasonryBackend::with_font_context(device_size, &mut self.plot_scene, font_ctx)
+            .with_font_scale(font_scale)
+            .with_stroke_styles(plot_ctx.stroke_styles.clone())
+            .with_batching(self.batching)
+            .into_drawing_area();
+        // Presenting draws the last batch of primitives when batching
+        let result = (self.plot)(&plot_ctx, &mut self.data, &root)
+            .and_then(|()| root.present().map_err(Into::into));
+        self.coord_mapping = plot_ctx
+            .coord_mapping
+            .into_inner()
+            .map(|mapping| mapping.scaled(scale_factor.recip()));
+        self.series = plot_ctx.series.into_inner();
+
+        // Don't report the same error again just because the plot was redrawn
+        let error = result.err().map(|err| err.to_string());
+        let new_error = error.clone().filter(|_| error != self.error);
+        self.error = error;
+        new_error
+    }
+
+    /// Submit the new error the plot function returned while painting, if there is one.
+    fn submit_paint_error(&mut self, ctx: &mut impl SubmitError) {
+        if let Some(err) = self.paint_error.take() {
+            ctx.submit_error(err);
+        }
+    }
+
+    /// Submit a [`PlotAction::Clicked`] if `event` finishes a click.
+    fn handle_click(&mut self, ctx: &mut EventCtx<'_>, event: &PointerEvent) {
+        match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button: Some(button),
+                state,
+                ..
+            }) => {
+                self.press = Some((ctx.local_position(state.position), *button));
+            }
+            PointerEvent::Up(PointerButtonEvent {
+                button: Some(button),
+                state,
+                ..
+            }) => {
+                if let Some((start, pressed)) = self.press.take()
+                    && pressed == *button
+                    && ctx.local_position(state.position).distance(start) <= CLICK_DISTANCE
+                    && let Some(mapping) = &self.coord_mapping
+                {
+                    let (data_x, data_y) = mapping.pixel_to_data(start);
+                    ctx.submit_action::<PlotAction>(PlotAction::Clicked {
+                        data_x,
+                        data_y,
+                        button: *button,
+                    });
+                }
+            }
+            PointerEvent::Cancel(_) => self.press = None,
+            _ => {}
+        }
+    }
+}
+
+impl Link {
+    /// Change the shared x range, waking the other plots of the group if it changed.
+    fn set_x(&self, x: Range<f64>) {
+        let changed = self.group.lock().set_x(x);
+        if changed {
+            self.wake_others();
+        }
+    }
+
+    /// Let the other plots of the group know that the shared state changed.
+    fn wake_others(&self) {
+        if let Some(id) = self.id {
+            self.group.wake(id);
+        }
+    }
+}
+
+impl PanZoom {
+    fn new(home: Viewport) -> Self {
+        Self {
+            current: home.clone(),
+            home,
+            drag: None,
+        }
+    }
+
+    /// Handles a pointer event, returning whether the viewport changed.
+    ///
+    /// `mapping` is the coordinate mapping of the plot, if the plot function registered one.
+    fn on_pointer_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        event: &PointerEvent,
+        mapping: Option<&CoordMapping>,
+    ) -> bool {
+        // Without a registered chart, assume that the viewport covers the whole widget
+        let area = match mapping {
+            Some(mapping) => mapping.area(),
+            None => Rect::from_origin_size(Point::ORIGIN, ctx.size()),
+        };
+        if area.width() <= 0. || area.height() <= 0. {
+            return false;
+        }
+
+        let viewport = match event {
+            PointerEvent::Down(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                state,
+                ..
+            }) => {
+                if state.count == 2 {
+                    self.drag = None;
+                    self.home.clone()
+                } else {
+                    ctx.capture_pointer();
+                    self.drag = Some(Drag {
+                        start: ctx.local_position(state.position),
+                        viewport: self.current.clone(),
+                    });
+                    return false;
+                }
+            }
+            PointerEvent::Move(PointerUpdate { current, .. }) => {
+                let Some(drag) = &self.drag else {
+                    return false;
+                };
+                let delta = ctx.local_position(current.position) - drag.start;
+                drag.viewport
+                    .panned((-delta.x / area.width(), delta.y / area.height()))
+            }
+            PointerEvent::Up(PointerButtonEvent {
+                button: Some(PointerButton::Primary),
+                ..
+            }) => {
+                self.drag = None;
+                return false;
+            }
+            PointerEvent::Cancel(_) => match self.drag.take() {
+                Some(drag) => drag.viewport,
+                None => return false,
+            },
+            PointerEvent::Scroll(PointerScrollEvent { delta, state, .. }) => {
+                let lines = match delta {
+                    ScrollDelta::LineDelta(_, y) => f64::from(*y),
+                    ScrollDelta::PixelDelta(position) => position.y / PIXELS_PER_LINE,
+                    ScrollDelta::PageDelta(..) => return false,
+                };
+                let position = ctx.local_position(state.position);
+                ctx.set_handled();
+                self.current.zoomed(
+                    ZOOM_PER_LINE.powf(lines),
+                    (
+                        (position.x - area.x0) / area.width(),
+                        (area.y1 - position.y) / area.height(),
+                    ),
+                )
+            }
+            _ => return false,
+        };
+
+        // Zooming too far runs into the limits of floating point numbers
+        if !viewport.is_valid() || viewport == self.current {
+            return false;
+        }
+        self.current = viewport;
+        true
+    }
+}
+
+impl<Data> Drop for Plot<Data> {
+    fn drop(&mut self) {
+        if let Some(Link {
+            group,
+            id: Some(id),
+            ..
+        }) = &self.link
+        {
+            group.leave(*id);
+        }
+    }
+}
+
+/// The contexts that can submit [`PlotAction::Error`].
+trait SubmitError {
+    fn submit_error(&mut self, err: String);
+}
+
+impl SubmitError for EventCtx<'_> {
+    fn submit_error(&mut self, err: String) {
+        self.submit_action::<PlotAction>(PlotAction::Error(err));
+    }
+}
+
+impl SubmitError for UpdateCtx<'_> {
+    fn submit_error(&mut self, err: String) {
+        self.submit_action::<PlotAction>(PlotAction::Error(err));
+    }
 }
 
 impl<Data: 'static> Widget for Plot<Data> {
-    type Action = NoAction;
+    type Action = PlotAction;
+
+    fn on_pointer_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &PointerEvent,
+    ) {
+        self.submit_paint_error(ctx);
+
+        let pointer = match event {
+            PointerEvent::Move(PointerUpdate { current, .. }) => {
+                Some(ctx.local_position(current.position))
+            }
+            PointerEvent::Leave(_) | PointerEvent::Cancel(_) => None,
+            _ => self.pointer,
+        };
+        if self.update_pointer(pointer) {
+            ctx.request_paint_only();
+        }
+
+        if self.handle_selection(ctx, event) {
+            return;
+        }
+
+        let mut viewport_changed = false;
+        let mut dragging = false;
+        if let Some(pan_zoom) = &mut self.pan_zoom {
+            viewport_changed = pan_zoom.on_pointer_event(ctx, event, self.coord_mapping.as_ref());
+            dragging = pan_zoom.drag.is_some();
+            if viewport_changed {
+                self.plot_generation += 1;
+                ctx.submit_action::<PlotAction>(PlotAction::ViewportChanged(
+                    pan_zoom.current.clone(),
+                ));
+                // Report the errors of drawing the new viewport, see `Plot::redraw`
+                ctx.request_paint_only();
+                ctx.request_anim_frame();
+                if let Some(link) = &self.link {
+                    link.set_x(pan_zoom.current.x.clone());
+                }
+            }
+        }
+
+        self.handle_click(ctx, event);
+
+        // Forget the hovered point while the plot moves, the registered points are outdated
+        let position = match event {
+            PointerEvent::Move(PointerUpdate { current, .. }) if !viewport_changed && !dragging => {
+                Some(ctx.local_position(current.position))
+            }
+            _ => None,
+        };
+        let group_changed = match event {
+            PointerEvent::Move(PointerUpdate { current, .. }) => {
+                let position = ctx.local_position(current.position);
+                self.update_group_hover(Some(position))
+            }
+            PointerEvent::Leave(_) => self.update_group_hover(None),
+            _ => false,
+        };
+        if group_changed {
+            ctx.request_paint_only();
+        }
+        if (matches!(event, PointerEvent::Move(_) | PointerEvent::Leave(_)) || viewport_changed)
+            && self.update_hovered(position)
+        {
+            ctx.submit_action::<PlotAction>(PlotAction::Hovered(self.hovered_point()));
+            if self.tooltip_style.is_some() {
+                ctx.request_paint_only();
+            }
+        }
+    }
+
+    fn on_anim_frame(
+        &mut self,
+        ctx: &mut UpdateCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        _interval: u64,
+    ) {
+        // Animation frames run before painting, so a frame requested together with the paint
+        // that runs the plot function has to wait for the next one
+        let painted =
+            matches!(self.plot_key, Some((generation, ..)) if generation == self.plot_generation);
+        if painted || ctx.is_stashed() {
+            self.submit_paint_error(ctx);
+        } else {
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, event: &Update) {
+        self.submit_paint_error(ctx);
+        match event {
+            Update::WidgetAdded => {
+                if let Some(link) = self.link.take() {
+                    self.join(link.group, ctx.widget_id());
+                }
+                // Submit the errors of the first paint, see `Plot::redraw`
+                ctx.request_anim_frame();
+            }
+            Update::HoveredChanged(false) => {
+                if self.update_group_hover(None) {
+                    ctx.request_paint_only();
+                }
+                if self.update_pointer(None) {
+                    ctx.request_paint_only();
+                }
+                if self.update_hovered(None) {
+                    ctx.submit_action::<PlotAction>(PlotAction::Hovered(None));
+                    if self.tooltip_style.is_some() {
+                        ctx.request_paint_only();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
 
     fn register_children(&mut self, _ctx: &mut masonry::core::RegisterCtx) {}
 
     fn layout(
         &mut self,
-        _ctx: &mut masonry::core::LayoutCtx,
-        _props: &mut masonry::core::PropertiesMut<'_>,
-        bc: &masonry::core::BoxConstraints,
-    ) -> masonry::kurbo::Size {
-        bc.max()
+        _ctx: &mut LayoutCtx,
+        _props: &mut PropertiesMut<'_>,
+        bc: &BoxConstraints,
+    ) -> Size {
+        self.sizing.layout(bc)
     }
 
     fn paint(
         &mut self,
-        ctx: &mut masonry::core::PaintCtx,
+        ctx: &mut PaintCtx,
         _props: &masonry::core::PropertiesRef<'_>,
-        scene: &mut masonry::vello::Scene,
+        scene: &mut Scene,
     ) {
-        let kurbo::Size { width, height } = ctx.size();
-        let size = (width as u32, height as u32);
-        let backend = MasonryBackend::new(size, scene, ctx);
+        let logical_size = ctx.size();
+        let size = (logical_size.width as u32, logical_size.height as u32);
+        // Plots without a waker only notice changes of the group when they are painted
+        self.sync_group_x();
+        let mut hover_x = None;
+        if let Some(link) = &mut self.link {
+            let state = link.group.lock();
+            link.generation = state.generation;
+            hover_x = state.hover_x;
+        }
+
+        // Paint can't submit actions, so new errors are submitted on the next animation frame
+        // or event
+        let scale_factor = ctx.get_scale_factor();
+        let (font_ctx, _) = ctx.text_contexts();
+        if let Some(err) = self.draw_plot(font_ctx, logical_size, scale_factor) {
+            self.paint_error = Some(err);
+        }
 
-        (self.plot)(size, &mut self.data, &backend.into_drawing_area());
+        if let Some(err) = &self.error {
+            let root = MasonryBackend::new(size, scene, ctx).into_drawing_area();
+            if let Err(err) = placeholder::draw(&root, &self.error_style, err) {
+                tracing::warn!("Failed to draw plot error: {err}");
+            }
+            return;
+        }
+
+        scene.append(&self.plot_scene, Some(Affine::scale(scale_factor.recip())));
+        let root = MasonryBackend::new(size, scene, ctx).into_drawing_area();
+
+        if let (Some(mapping), Some(x)) = (&self.coord_mapping, hover_x) {
+            // Plots without a crosshair of their own still show the group's in the default style
+            let style = self.crosshair_style.clone().unwrap_or_default();
+            if let Err(err) = group::draw_crosshair(&root, &style, mapping, x) {
+                tracing::warn!("Failed to draw plot crosshair: {err}");
+            }
+        }
+
+        if let (Some(style), Some(mapping), Some(pointer)) =
+            (&self.crosshair_style, &self.coord_mapping, self.pointer)
+            && let Err(err) = crosshair::draw(&root, style, mapping, pointer)
+        {
+            tracing::warn!("Failed to draw plot crosshair: {err}");
+        }
+
+        if let (Some(selection), Some(mapping)) = (&self.selection, &self.coord_mapping)
+            && let Err(err) = selection::draw(&root, selection.rect(mapping.area()))
+        {
+            tracing::warn!("Failed to draw plot selection: {err}");
+        }
+
+        if let (Some(style), Some(mapping), Some(hovered)) = (
+            &self.tooltip_style,
+            &self.coord_mapping,
+            self.hovered_point(),
+        ) {
+            let point = (hovered.data_x, hovered.data_y);
+            let position = mapping.data_to_pixel(point);
+            if let Err(err) = tooltip::draw(&root, style, &hovered.series_name, point, position) {
+                tracing::warn!("Failed to draw plot tooltip: {err}");
+            }
+        }
     }
 
     fn accessibility_role(&self) -> Role {
@@ -111,7 +1277,51 @@ impl<Data: 'static> Widget for Plot<Data> {
         node.set_value(&*self.alt_text);
     }
 
-    fn children_ids(&self) -> SmallVec<[masonry::core::WidgetId; 16]> {
+    fn children_ids(&self) -> SmallVec<[WidgetId; 16]> {
         SmallVec::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use masonry::kurbo::Size;
+    use masonry::parley::FontContext;
+    use plotters::prelude::*;
+
+    use super::Plot;
+
+    #[test]
+    fn plot_scene_is_reused() {
+        let runs = Rc::new(Cell::new(0));
+        let counter = runs.clone();
+        let mut plot = Plot::new(
+            (),
+            move |_ctx, _data, root| {
+                counter.set(counter.get() + 1);
+                root.fill(&WHITE)?;
+                Ok(())
+            },
+            "plot",
+        );
+        let mut font_ctx = FontContext::new();
+
+        // Repainting, e.g. for a tooltip, doesn't run the plot function again
+        let size = Size::new(100., 100.);
+        plot.draw_plot(&mut font_ctx, size, 1.);
+        plot.draw_plot(&mut font_ctx, size, 1.);
+        assert_eq!(runs.get(), 1);
+
+        plot.plot_generation += 1;
+        plot.draw_plot(&mut font_ctx, size, 1.);
+        assert_eq!(runs.get(), 2);
+
+        plot.draw_plot(&mut font_ctx, Size::new(200., 100.), 1.);
+        assert_eq!(runs.get(), 3);
+
+        plot.draw_plot(&mut font_ctx, Size::new(200., 100.), 2.);
+        assert_eq!(runs.get(), 4);
+    }
+}
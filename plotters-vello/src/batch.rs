@@ -0,0 +1,71 @@
+use vello::kurbo::{BezPath, Shape, Stroke};
+use vello::peniko;
+
+/// The tolerance used to turn circles into Bézier curves when adding them to a batch.
+const TOLERANCE: f64 = 0.1;
+
+/// How the primitives of a [`Batch`] are drawn.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BatchStyle {
+    /// Filled with `color`, with the geometry placed on pixel centers if `mid` is set and on
+    /// pixel corners otherwise.
+    Fill { color: peniko::Color, mid: bool },
+    /// Stroked with `color`, with the geometry placed on pixel centers.
+    Stroke {
+        color: peniko::Color,
+        stroke: Stroke,
+    },
+}
+
+/// Consecutive primitives of the same style, collected into a single path so that they are
+/// drawn with a single fill or stroke.
+#[derive(Debug)]
+pub(crate) struct Batch {
+    pub(crate) style: BatchStyle,
+    pub(crate) path: BezPath,
+}
+
+impl Batch {
+    pub(crate) fn new(style: BatchStyle) -> Self {
+        Self {
+            style,
+            path: BezPath::new(),
+        }
+    }
+
+    /// Add `shape` as a new subpath.
+    pub(crate) fn push(&mut self, shape: &impl Shape) {
+        self.path.extend(shape.path_elements(TOLERANCE));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plotters::prelude::*;
+    use vello::Scene;
+
+    use crate::VelloBackend;
+
+    #[test]
+    fn batch_same_style() {
+        let mut scene = Scene::new();
+        let root = VelloBackend::new((100, 100), &mut scene)
+            .with_batching(true)
+            .into_drawing_area();
+        for i in 0..10 {
+            root.draw(&Circle::new((i * 10, 50), 3, RED.filled()))
+                .unwrap();
+        }
+        root.draw(&PathElement::new([(0, 0), (10, 10)], BLUE))
+            .unwrap();
+        root.draw(&PathElement::new([(10, 0), (20, 10)], BLUE))
+            .unwrap();
+        // Nothing is drawn before the batch is flushed
+        root.draw(&Circle::new((0, 0), 3, RED.filled())).unwrap();
+        root.present().unwrap();
+        drop(root);
+
+        // The red circles, the blue lines and the last circle
+        assert_eq!(scene.encoding().n_paths, 3);
+    }
+}
@@ -0,0 +1,62 @@
+/// The pixel layout of the data passed to
+/// [`DrawingBackend::blit_bitmap`](plotters_backend::DrawingBackend::blit_bitmap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapFormat {
+    /// Tightly packed RGB with 3 bytes per pixel. This is what plotters'
+    /// `BitMapElement` uses by default.
+    Rgb,
+    /// RGBA with 4 bytes per pixel, not premultiplied.
+    Rgba,
+    /// BGR with 4 bytes per pixel, the last byte of each pixel is ignored.
+    Bgrx,
+}
+
+impl BitmapFormat {
+    /// The number of bytes used by a single pixel.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            BitmapFormat::Rgb => 3,
+            BitmapFormat::Rgba | BitmapFormat::Bgrx => 4,
+        }
+    }
+
+    /// Guess the format of `len` bytes of pixel data for an image of `pixels` pixels.
+    ///
+    /// BGRX can't be told apart from RGBA, so 4 bytes per pixel are always taken to be RGBA.
+    fn detect(len: usize, pixels: usize) -> Option<Self> {
+        [BitmapFormat::Rgb, BitmapFormat::Rgba]
+            .into_iter()
+            .find(|format| pixels.checked_mul(format.bytes_per_pixel()) == Some(len))
+    }
+}
+
+/// Converts the pixel data of an `width` by `height` image to RGBA.
+///
+/// If `format` is `None` the format is detected from the length of `src`. Returns `None`
+/// if the length of `src` doesn't match the image dimensions.
+pub(crate) fn to_rgba8(
+    src: &[u8],
+    (width, height): (u32, u32),
+    format: Option<BitmapFormat>,
+) -> Option<Vec<u8>> {
+    let pixels = (width as usize).checked_mul(height as usize)?;
+    let format = match format {
+        Some(format) => format,
+        None => BitmapFormat::detect(src.len(), pixels)?,
+    };
+    if pixels.checked_mul(format.bytes_per_pixel()) != Some(src.len()) {
+        return None;
+    }
+
+    Some(match format {
+        BitmapFormat::Rgba => src.to_vec(),
+        BitmapFormat::Rgb => src
+            .chunks_exact(3)
+            .flat_map(|px| [px[0], px[1], px[2], 255])
+            .collect(),
+        BitmapFormat::Bgrx => src
+            .chunks_exact(4)
+            .flat_map(|px| [px[2], px[1], px[0], 255])
+            .collect(),
+    })
+}
@@ -0,0 +1,115 @@
+use parley::GlyphRun;
+use vello::kurbo::{Affine, Shape, Stroke};
+use vello::{Glyph, Scene, peniko};
+
+use crate::Error;
+
+/// A render target that [`VelloBackend`](crate::VelloBackend) can draw into.
+///
+/// This is implemented for [`vello::Scene`] and, with the `cpu` feature enabled, for
+/// [`vello_cpu::RenderContext`].
+pub trait Canvas {
+    /// Fill `shape` with a solid color.
+    fn fill(&mut self, transform: Affine, color: peniko::Color, shape: &impl Shape);
+
+    /// Stroke the outline of `shape` with a solid color.
+    fn stroke(
+        &mut self,
+        style: &Stroke,
+        transform: Affine,
+        color: peniko::Color,
+        shape: &impl Shape,
+    );
+
+    /// Draw an image with its upper left corner at the origin of `transform`.
+    ///
+    /// Fails with [`Error::Renderer`] if the render target can't draw the image.
+    fn draw_image(&mut self, image: &peniko::ImageData, transform: Affine) -> Result<(), Error>;
+
+    /// Draw a run of glyphs laid out by parley.
+    fn draw_glyph_run(
+        &mut self,
+        glyph_run: &GlyphRun<'_, peniko::Brush>,
+        transform: Affine,
+        hint: bool,
+    );
+
+    /// Clip everything drawn until the matching [`Canvas::pop_clip`] to `shape`.
+    fn push_clip(&mut self, transform: Affine, shape: &impl Shape);
+
+    /// Remove the clip pushed last.
+    fn pop_clip(&mut self);
+}
+
+impl Canvas for Scene {
+    fn fill(&mut self, transform: Affine, color: peniko::Color, shape: &impl Shape) {
+        Scene::fill(self, peniko::Fill::NonZero, transform, color, None, shape);
+    }
+
+    fn stroke(
+        &mut self,
+        style: &Stroke,
+        transform: Affine,
+        color: peniko::Color,
+        shape: &impl Shape,
+    ) {
+        Scene::stroke(self, style, transform, color, None, shape);
+    }
+
+    fn draw_image(&mut self, image: &peniko::ImageData, transform: Affine) -> Result<(), Error> {
+        let brush = peniko::ImageBrush::new(image.clone());
+        Scene::draw_image(self, &brush, transform);
+        Ok(())
+    }
+
+    fn draw_glyph_run(
+        &mut self,
+        glyph_run: &GlyphRun<'_, peniko::Brush>,
+        transform: Affine,
+        hint: bool,
+    ) {
+        let run = glyph_run.run();
+        self.draw_glyphs(run.font())
+            .brush(&glyph_run.style().brush)
+            .hint(hint)
+            .transform(transform)
+            .glyph_transform(glyph_transform(glyph_run))
+            .font_size(run.font_size())
+            .normalized_coords(run.normalized_coords())
+            .draw(
+                peniko::Fill::NonZero,
+                positioned_glyphs(glyph_run).map(|(id, x, y)| Glyph { id, x, y }),
+            );
+    }
+
+    fn push_clip(&mut self, transform: Affine, shape: &impl Shape) {
+        self.push_clip_layer(transform, shape);
+    }
+
+    fn pop_clip(&mut self) {
+        self.pop_layer();
+    }
+}
+
+/// The skew applied to synthesized oblique glyphs.
+pub(crate) fn glyph_transform(glyph_run: &GlyphRun<'_, peniko::Brush>) -> Option<Affine> {
+    glyph_run
+        .run()
+        .synthesis()
+        .skew()
+        .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0))
+}
+
+/// The ids and positions of the glyphs in a glyph run.
+pub(crate) fn positioned_glyphs<'a>(
+    glyph_run: &'a GlyphRun<'a, peniko::Brush>,
+) -> impl Iterator<Item = (u32, f32, f32)> + 'a {
+    let mut x = glyph_run.offset();
+    let y = glyph_run.baseline();
+    glyph_run.glyphs().map(move |glyph| {
+        let gx = x + glyph.x;
+        let gy = y - glyph.y;
+        x += glyph.advance;
+        (glyph.id, gx, gy)
+    })
+}
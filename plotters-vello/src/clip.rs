@@ -0,0 +1,130 @@
+use std::cell::{Cell, Ref, RefCell};
+use std::rc::Rc;
+
+use plotters_backend::BackendCoord;
+use vello::kurbo::Rect;
+
+/// The rectangles a [`VelloBackend`](crate::VelloBackend) clips its drawing to.
+///
+/// Like [`StrokeStyles`](crate::StrokeStyles) this is a handle that can be shared. Keep a
+/// clone of the handle passed to
+/// [`VelloBackend::with_clip_stack`](crate::VelloBackend::with_clip_stack) to clip while
+/// drawing, e.g. to keep series that overshoot the chart inside its plotting area:
+///
+/// ```rust
+/// # use plotters::prelude::*;
+/// # use plotters_vello::{ClipStack, VelloBackend};
+/// # let mut scene = vello::Scene::new();
+/// let clips = ClipStack::new();
+/// let root = VelloBackend::new((400, 300), &mut scene)
+///     .with_clip_stack(clips.clone())
+///     .into_drawing_area();
+/// let mut chart = ChartBuilder::on(&root).build_cartesian_2d(0.0..1.0, 0.0..1.0)?;
+///
+/// let (x, y) = chart.plotting_area().get_pixel_range();
+/// clips.push((x.start, y.start), (x.end - 1, y.end - 1));
+/// chart.draw_series(LineSeries::new([(0.0, 0.0), (2.0, 2.0)], &RED))?;
+/// clips.pop();
+/// root.present()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ClipStack {
+    rects: Rc<RefCell<Vec<(BackendCoord, BackendCoord)>>>,
+    /// Counts the changes to the stack, so backends only compare the rectangles after a change.
+    generation: Rc<Cell<u64>>,
+}
+
+impl ClipStack {
+    /// An empty stack that doesn't clip anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clip everything drawn from now on to the pixels from `upper_left` up to and including
+    /// `bottom_right`, in addition to the clips already on the stack.
+    pub fn push(&self, upper_left: BackendCoord, bottom_right: BackendCoord) {
+        self.rects.borrow_mut().push((upper_left, bottom_right));
+        self.generation.set(self.generation.get() + 1);
+    }
+
+    /// Remove the clip pushed last and return its corners.
+    pub fn pop(&self) -> Option<(BackendCoord, BackendCoord)> {
+        self.generation.set(self.generation.get() + 1);
+        self.rects.borrow_mut().pop()
+    }
+
+    /// The number of clips on the stack.
+    pub fn len(&self) -> usize {
+        self.rects.borrow().len()
+    }
+
+    /// Whether the stack is empty.
+    pub fn is_empty(&self) -> bool {
+        self.rects.borrow().is_empty()
+    }
+
+    pub(crate) fn rects(&self) -> Ref<'_, Vec<(BackendCoord, BackendCoord)>> {
+        self.rects.borrow()
+    }
+
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.get()
+    }
+}
+
+/// The area covered by the pixels from `upper_left` up to and including `bottom_right`.
+pub(crate) fn clip_rect((upper_left, bottom_right): (BackendCoord, BackendCoord)) -> Rect {
+    Rect::new(
+        upper_left.0 as f64,
+        upper_left.1 as f64,
+        bottom_right.0 as f64 + 1.,
+        bottom_right.1 as f64 + 1.,
+    )
+    .abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use plotters::prelude::*;
+    use vello::Scene;
+    use vello::kurbo::Affine;
+
+    use super::ClipStack;
+    use crate::VelloBackend;
+
+    #[test]
+    fn clip_layers() {
+        let mut scene = Scene::new();
+        let clips = ClipStack::new();
+        let backend = VelloBackend::new((100, 100), &mut scene)
+            .with_transform(Affine::translate((50., 50.)))
+            .with_clip_to_size()
+            .with_clip_stack(clips.clone());
+        let root = backend.into_drawing_area();
+
+        root.fill(&WHITE).unwrap();
+        clips.push((10, 10), (20, 20));
+        root.draw_pixel((15, 15), &RED).unwrap();
+        clips.push((12, 12), (18, 18));
+        root.draw_pixel((15, 15), &RED).unwrap();
+        clips.pop();
+        clips.pop();
+        root.draw_pixel((15, 15), &RED).unwrap();
+        clips.push((0, 0), (1, 1));
+        root.draw_pixel((0, 0), &RED).unwrap();
+        root.present().unwrap();
+        drop(root);
+
+        let encoding = scene.encoding();
+        // The size clip and three clips from the stack, all closed again
+        assert_eq!(encoding.n_clips, 8);
+        assert_eq!(encoding.n_open_clips, 0);
+        assert!(
+            encoding
+                .transforms
+                .iter()
+                .all(|transform| transform.translation == [50., 50.])
+        );
+    }
+}
@@ -0,0 +1,279 @@
+/*!
+Rasterize plots on the CPU using [vello_cpu](https://crates.io/crates/vello_cpu).
+
+This doesn't need a GPU, so it can be used to render plots on headless machines.
+It is only available with the `cpu` feature enabled.
+
+```rust
+# use plotters::prelude::*;
+let rgba = plotters_vello::cpu::render_to_rgba(640, 480, |backend| {
+    let root = backend.into_drawing_area();
+    root.fill(&WHITE)?;
+    // see the plotters documentation on how to draw a chart on `root`
+    Ok::<_, Box<dyn std::error::Error>>(())
+})
+.unwrap();
+assert_eq!(rgba.len(), 640 * 480 * 4);
+```
+*/
+
+use std::path::Path;
+
+use parley::GlyphRun;
+use plotters_backend::DrawingBackend;
+use vello::kurbo::{Affine, Shape, Stroke};
+use vello::peniko;
+pub use vello_cpu::RenderContext;
+use vello_cpu::{Glyph, Image, ImageSource, Pixmap, kurbo as cpu_kurbo, peniko as cpu_peniko};
+
+use crate::canvas::{glyph_transform, positioned_glyphs};
+use crate::{Error, VelloBackend};
+
+/// The tolerance used when flattening curves before handing them to vello_cpu.
+const TOLERANCE: f64 = 0.1;
+
+impl crate::Canvas for RenderContext {
+    fn fill(&mut self, transform: Affine, color: peniko::Color, shape: &impl Shape) {
+        self.set_transform(convert_affine(transform));
+        self.set_paint(color);
+        self.fill_path(&convert_shape(shape));
+    }
+
+    fn stroke(
+        &mut self,
+        style: &Stroke,
+        transform: Affine,
+        color: peniko::Color,
+        shape: &impl Shape,
+    ) {
+        self.set_transform(convert_affine(transform));
+        self.set_paint(color);
+        self.set_stroke(convert_stroke(style));
+        self.stroke_path(&convert_shape(shape));
+    }
+
+    fn draw_image(&mut self, image: &peniko::ImageData, transform: Affine) -> Result<(), Error> {
+        if image.width > u16::MAX.into() || image.height > u16::MAX.into() {
+            return Err(Error::Renderer {
+                reason: format!(
+                    "{}x{} image exceeds the maximum image size of vello_cpu",
+                    image.width, image.height
+                ),
+            });
+        }
+
+        let format = match image.format {
+            peniko::ImageFormat::Bgra8 => cpu_peniko::ImageFormat::Bgra8,
+            _ => cpu_peniko::ImageFormat::Rgba8,
+        };
+        let alpha_type = match image.alpha_type {
+            peniko::ImageAlphaType::AlphaPremultiplied => {
+                cpu_peniko::ImageAlphaType::AlphaPremultiplied
+            }
+            _ => cpu_peniko::ImageAlphaType::Alpha,
+        };
+        let source = ImageSource::from_peniko_image_data(&cpu_peniko::ImageData {
+            data: image.data.clone(),
+            format,
+            alpha_type,
+            width: image.width,
+            height: image.height,
+        });
+
+        self.set_transform(convert_affine(transform));
+        self.set_paint(Image {
+            image: source,
+            sampler: Default::default(),
+        });
+        self.fill_rect(&cpu_kurbo::Rect::new(
+            0.,
+            0.,
+            image.width as f64,
+            image.height as f64,
+        ));
+        Ok(())
+    }
+
+    fn draw_glyph_run(
+        &mut self,
+        glyph_run: &GlyphRun<'_, peniko::Brush>,
+        transform: Affine,
+        hint: bool,
+    ) {
+        let peniko::Brush::Solid(color) = glyph_run.style().brush else {
+            return;
+        };
+        let run = glyph_run.run();
+        self.set_transform(convert_affine(transform));
+        self.set_paint(color);
+        let mut builder = self
+            .glyph_run(run.font())
+            .font_size(run.font_size())
+            .hint(hint)
+            .normalized_coords(run.normalized_coords());
+        if let Some(glyph_transform) = glyph_transform(glyph_run) {
+            builder = builder.glyph_transform(convert_affine(glyph_transform));
+        }
+        builder.fill_glyphs(positioned_glyphs(glyph_run).map(|(id, x, y)| Glyph { id, x, y }));
+    }
+
+    fn push_clip(&mut self, transform: Affine, shape: &impl Shape) {
+        // The clip path is placed with the current transform
+        self.set_transform(convert_affine(transform));
+        self.push_clip_layer(&convert_shape(shape));
+    }
+
+    fn pop_clip(&mut self) {
+        self.pop_layer();
+    }
+}
+
+/// Draw a plot with a [`VelloBackend`] and rasterize it on the CPU.
+///
+/// Returns the pixels of the plot as unpremultiplied RGBA with 8 bits per channel, in
+/// row-major order. Pixels that the plot doesn't cover are transparent.
+///
+/// The backend is presented once `draw` returns, so the last batch is drawn and all clip
+/// layers are closed even if `draw` doesn't present the drawing area.
+pub fn render_to_rgba<E>(
+    width: u16,
+    height: u16,
+    draw: impl FnOnce(&mut VelloBackend<'_, RenderContext>) -> Result<(), E>,
+) -> Result<Vec<u8>, E> {
+    let pixmap = render_to_pixmap(width, height, draw)?;
+    Ok(pixmap
+        .take_unpremultiplied()
+        .into_iter()
+        .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, pixel.a])
+        .collect())
+}
+
+/// Draw a plot with a [`VelloBackend`], rasterize it on the CPU and save it as a PNG
+/// file at `path`.
+///
+/// Like with [`render_to_rgba`], the backend is presented once `draw` returns.
+pub fn save_png<E: Into<Box<dyn std::error::Error>>>(
+    path: impl AsRef<Path>,
+    width: u16,
+    height: u16,
+    draw: impl FnOnce(&mut VelloBackend<'_, RenderContext>) -> Result<(), E>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let pixmap = render_to_pixmap(width, height, draw).map_err(Into::into)?;
+    let png = pixmap.into_png().map_err(|err| Error::Renderer {
+        reason: err.to_string(),
+    })?;
+    std::fs::write(path, png)?;
+    Ok(())
+}
+
+fn render_to_pixmap<E>(
+    width: u16,
+    height: u16,
+    draw: impl FnOnce(&mut VelloBackend<'_, RenderContext>) -> Result<(), E>,
+) -> Result<Pixmap, E> {
+    let mut ctx = RenderContext::new(width, height);
+    let mut backend = VelloBackend::new((width.into(), height.into()), &mut ctx);
+    draw(&mut backend)?;
+    // Draw the last batch and close the clip layers in case `draw` didn't present the plot,
+    // presenting a `VelloBackend` never fails
+    let _ = backend.present();
+    ctx.flush();
+
+    let mut pixmap = Pixmap::new(width, height);
+    ctx.render_to_pixmap(&mut pixmap);
+    Ok(pixmap)
+}
+
+// vello_cpu uses a newer version of kurbo than vello, so geometry has to be converted.
+
+fn convert_point(point: vello::kurbo::Point) -> cpu_kurbo::Point {
+    cpu_kurbo::Point::new(point.x, point.y)
+}
+
+fn convert_affine(affine: Affine) -> cpu_kurbo::Affine {
+    cpu_kurbo::Affine::new(affine.as_coeffs())
+}
+
+fn convert_shape(shape: &impl Shape) -> cpu_kurbo::BezPath {
+    use vello::kurbo::PathEl;
+
+    shape
+        .path_elements(TOLERANCE)
+        .map(|el| match el {
+            PathEl::MoveTo(p) => cpu_kurbo::PathEl::MoveTo(convert_point(p)),
+            PathEl::LineTo(p) => cpu_kurbo::PathEl::LineTo(convert_point(p)),
+            PathEl::QuadTo(p1, p2) => {
+                cpu_kurbo::PathEl::QuadTo(convert_point(p1), convert_point(p2))
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                cpu_kurbo::PathEl::CurveTo(convert_point(p1), convert_point(p2), convert_point(p3))
+            }
+            PathEl::ClosePath => cpu_kurbo::PathEl::ClosePath,
+        })
+        .collect()
+}
+
+fn convert_stroke(stroke: &Stroke) -> cpu_kurbo::Stroke {
+    use vello::kurbo::{Cap, Join};
+
+    let convert_cap = |cap| match cap {
+        Cap::Butt => cpu_kurbo::Cap::Butt,
+        Cap::Square => cpu_kurbo::Cap::Square,
+        Cap::Round => cpu_kurbo::Cap::Round,
+    };
+
+    cpu_kurbo::Stroke {
+        width: stroke.width,
+        join: match stroke.join {
+            Join::Bevel => cpu_kurbo::Join::Bevel,
+            Join::Miter => cpu_kurbo::Join::Miter,
+            Join::Round => cpu_kurbo::Join::Round,
+        },
+        miter_limit: stroke.miter_limit,
+        start_cap: convert_cap(stroke.start_cap),
+        end_cap: convert_cap(stroke.end_cap),
+        dash_pattern: stroke.dash_pattern.iter().copied().collect(),
+        dash_offset: stroke.dash_offset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plotters::prelude::*;
+
+    #[test]
+    fn fill_root_white() {
+        let rgba = super::render_to_rgba(3, 2, |backend| backend.into_drawing_area().fill(&WHITE))
+            .unwrap();
+
+        assert_eq!(&rgba, &[255; 6 * 4]);
+    }
+
+    #[test]
+    fn draw_text() {
+        let rgba = super::render_to_rgba(40, 20, |backend| {
+            let root = backend.into_drawing_area();
+            root.fill(&WHITE)?;
+            root.draw_text(
+                "y=x^2",
+                &("sans-serif", 16).into_font().color(&BLACK),
+                (2, 2),
+            )
+        })
+        .unwrap();
+
+        assert!(rgba.chunks(4).any(|pixel| pixel[0] < 128));
+    }
+
+    #[test]
+    fn present_after_drawing() {
+        let rgba = super::render_to_rgba(2, 1, |backend| {
+            backend.push_clip((0, 0), (0, 0));
+            // Dropped without presenting
+            backend.into_drawing_area().fill(&WHITE)
+        })
+        .unwrap();
+
+        assert_eq!(&rgba, &[255, 255, 255, 255, 0, 0, 0, 0]);
+    }
+}
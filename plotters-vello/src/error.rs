@@ -0,0 +1,62 @@
+use crate::BitmapFormat;
+
+/// The error type of [`VelloBackend`](crate::VelloBackend).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The pixel data passed to `blit_bitmap` doesn't match the size of the bitmap.
+    InvalidBitmap {
+        width: u32,
+        height: u32,
+        len: usize,
+        format: Option<BitmapFormat>,
+    },
+    /// A piece of text could not be laid out.
+    TextLayout { text: String, reason: String },
+    /// None of the available fonts could be used to draw a piece of text.
+    MissingFont { family: String, text: String },
+    /// The geometry of a primitive contains NaN or infinite coordinates.
+    NonFiniteGeometry { primitive: &'static str },
+    /// The render target failed to draw or rasterize the plot.
+    Renderer { reason: String },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidBitmap {
+                width,
+                height,
+                len,
+                format: Some(format),
+            } => write!(
+                f,
+                "{len} bytes of pixel data don't match a {width}x{height} {format:?} bitmap"
+            ),
+            Error::InvalidBitmap {
+                width,
+                height,
+                len,
+                format: None,
+            } => write!(
+                f,
+                "{len} bytes of pixel data don't match a {width}x{height} RGB or RGBA bitmap"
+            ),
+            Error::TextLayout { text, reason } => {
+                write!(f, "failed to lay out text {text:?}: {reason}")
+            }
+            Error::MissingFont { family, text } => {
+                write!(
+                    f,
+                    "no font available for family {family:?} to draw {text:?}"
+                )
+            }
+            Error::NonFiniteGeometry { primitive } => {
+                write!(f, "{primitive} has non-finite coordinates")
+            }
+            Error::Renderer { reason } => write!(f, "renderer failed: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -1,30 +1,82 @@
 /*!
-A [Piet](https://crates.io/crates/piet) backend for [Plotters](https://crates.io/crates/plotters). This lets you draw plots on a Piet render context.
+A [Vello](https://crates.io/crates/vello) backend for [Plotters](https://crates.io/crates/plotters). This lets you draw plots on a Vello scene.
+
+Plots are drawn with [`VelloBackend`]. [`RecordingBackend`] records them into a [`DisplayList`]
+instead, which can be drawn later or, with the `svg` feature, exported as SVG. With the `cpu`
+feature, the `cpu` module rasterizes plots without a GPU.
 */
 
+use std::cell::RefCell;
+
 use plotters_backend::{BackendColor, BackendCoord, DrawingBackend, DrawingErrorKind};
 use vello::{Scene, kurbo, peniko};
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Error {}
-
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "plotters-vello error")
-    }
-}
-
-impl std::error::Error for Error {}
+mod batch;
+mod bitmap;
+mod canvas;
+mod clip;
+#[cfg(feature = "cpu")]
+pub mod cpu;
+mod error;
+mod recording;
+mod stroke;
+#[cfg(feature = "svg")]
+mod svg;
+mod text;
+
+pub use bitmap::BitmapFormat;
+pub use canvas::Canvas;
+pub use clip::ClipStack;
+pub use error::Error;
+pub use parley;
+pub use recording::{DisplayList, DrawCommand, RecordedStyle, RecordedTextStyle, RecordingBackend};
+pub use stroke::{StrokeStyles, StrokedPrimitive, VelloStrokeStyle};
+
+use crate::batch::{Batch, BatchStyle};
+use crate::text::TextContext;
 
 /// The Vello backend.
 ///
 /// Note that the size of the Vello scene has to be specified here.
-pub struct VelloBackend<'a> {
+///
+/// Text is laid out with [parley](https://crates.io/crates/parley). By default the backend
+/// creates its own [`parley::FontContext`] the first time it draws text, use
+/// [`VelloBackend::with_font_context`] to share an existing one instead.
+///
+/// Plots are usually drawn into a [`vello::Scene`], but any [`Canvas`] can be used as
+/// the render target.
+///
+/// Joins, caps and dashes of strokes are configured with [`VelloBackend::with_stroke_styles`].
+///
+/// Plotters places everything on whole pixels, use [`VelloBackend::with_supersampling`] for
+/// smoother curves.
+///
+/// The plot is drawn at the origin of the scene unless it is moved with
+/// [`VelloBackend::with_transform`]. Drawing isn't clipped, use
+/// [`VelloBackend::with_clip_to_size`] to keep the plot inside its size and [`ClipStack`] to
+/// clip parts of it. Clip layers stay open until the drawing area is presented.
+///
+/// Plots with many primitives draw faster with [`VelloBackend::with_batching`].
+pub struct VelloBackend<'a, C: Canvas = Scene> {
     size: (u32, u32),
-    scene: &'a mut Scene,
+    scene: &'a mut C,
+    text: RefCell<TextContext<'a>>,
+    bitmap_format: Option<BitmapFormat>,
+    stroke_styles: StrokeStyles,
+    supersampling: u32,
+    transform: kurbo::Affine,
+    clip_to_size: bool,
+    clip_stack: ClipStack,
+    /// The clip layers pushed to the scene, the size clip followed by the clips of the stack.
+    size_clipped: bool,
+    pushed_clips: Vec<(BackendCoord, BackendCoord)>,
+    /// The generation of the clip stack when the pushed clips last matched it.
+    synced_clips: Option<u64>,
+    batching: bool,
+    batch: Option<Batch>,
 }
 
-impl std::fmt::Debug for VelloBackend<'_> {
+impl<C: Canvas> std::fmt::Debug for VelloBackend<'_, C> {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         fmt.debug_struct("VelloBackend")
             .field("size", &self.size)
@@ -32,29 +84,335 @@ impl std::fmt::Debug for VelloBackend<'_> {
     }
 }
 
-impl<'a> VelloBackend<'a> {
-    pub fn new(size: (u32, u32), scene: &'a mut Scene) -> Self {
-        Self { size, scene }
+impl<'a, C: Canvas> VelloBackend<'a, C> {
+    pub fn new(size: (u32, u32), scene: &'a mut C) -> Self {
+        Self {
+            size,
+            scene,
+            text: RefCell::new(TextContext::new()),
+            bitmap_format: None,
+            stroke_styles: StrokeStyles::new(),
+            supersampling: 1,
+            transform: kurbo::Affine::IDENTITY,
+            clip_to_size: false,
+            clip_stack: ClipStack::new(),
+            size_clipped: false,
+            pushed_clips: Vec::new(),
+            synced_clips: None,
+            batching: false,
+            batch: None,
+        }
+    }
+
+    /// Create a backend that lays out text using the fonts of `font_ctx`.
+    pub fn with_font_context(
+        size: (u32, u32),
+        scene: &'a mut C,
+        font_ctx: &'a mut parley::FontContext,
+    ) -> Self {
+        Self {
+            text: RefCell::new(TextContext::with_font_context(font_ctx)),
+            ..Self::new(size, scene)
+        }
+    }
+
+    /// Set the pixel layout of bitmaps drawn with this backend.
+    ///
+    /// By default the layout is detected from the size of the pixel data, which works for
+    /// RGB and RGBA bitmaps. BGRX bitmaps always need to be configured explicitly.
+    pub fn with_bitmap_format(mut self, format: BitmapFormat) -> Self {
+        self.bitmap_format = Some(format);
+        self
+    }
+
+    /// Stroke outlines with `styles` instead of the default styles.
+    ///
+    /// See [`StrokeStyles`] on how to change the styles while drawing.
+    pub fn with_stroke_styles(mut self, styles: StrokeStyles) -> Self {
+        self.stroke_styles = styles;
+        self
+    }
+
+    /// The styles outlines are stroked with.
+    pub fn stroke_styles(&self) -> &StrokeStyles {
+        &self.stroke_styles
+    }
+
+    /// Let plotters draw on a grid `factor` times finer than the pixels of the scene.
+    ///
+    /// Plotters rounds all coordinates to whole pixels, which makes line series with many
+    /// points look jagged. With supersampling the backend reports a size `factor` times
+    /// larger than the scene to plotters and scales everything down again when drawing into
+    /// the scene. Stroke widths, dashes, circle radii and font sizes are scaled up so that
+    /// they look the same as without supersampling.
+    ///
+    /// Other sizes given in pixels, like the margins and label area sizes of a
+    /// `ChartBuilder`, are measured on the finer grid, use [`VelloBackend::supersampling`]
+    /// to scale them:
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_vello::VelloBackend;
+    /// # let mut scene = vello::Scene::new();
+    /// let backend = VelloBackend::new((400, 300), &mut scene).with_supersampling(4);
+    /// let factor = backend.supersampling();
+    /// let root = backend.into_drawing_area();
+    /// let chart = ChartBuilder::on(&root)
+    ///     .margin(5 * factor)
+    ///     .x_label_area_size(30 * factor)
+    ///     .y_label_area_size(30 * factor)
+    ///     .build_cartesian_2d(0.0..1.0, 0.0..1.0)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn with_supersampling(mut self, factor: u32) -> Self {
+        self.supersampling = factor.max(1);
+        self
+    }
+
+    /// How many times finer than the pixels of the scene plotters draws, see
+    /// [`VelloBackend::with_supersampling`].
+    pub fn supersampling(&self) -> u32 {
+        self.supersampling
+    }
+
+    /// Place the plot in the scene with `transform`, e.g. to draw it at an offset inside a
+    /// larger scene.
+    ///
+    /// The transform is applied to everything the backend draws, including text and clips.
+    pub fn with_transform(mut self, transform: kurbo::Affine) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// The transform the plot is placed in the scene with.
+    pub fn transform(&self) -> kurbo::Affine {
+        self.transform
+    }
+
+    /// Clip everything the backend draws to its size, so nothing bleeds into the rest of the
+    /// scene.
+    ///
+    /// The clip layer is pushed before the first primitive is drawn and popped by
+    /// `present`, so call `present` on the drawing area once the plot is finished.
+    pub fn with_clip_to_size(mut self) -> Self {
+        self.clip_to_size = true;
+        self
+    }
+
+    /// Clip drawing to the rectangles on `clip_stack`.
+    ///
+    /// See [`ClipStack`] on how to change the clips while drawing.
+    pub fn with_clip_stack(mut self, clip_stack: ClipStack) -> Self {
+        self.clip_stack = clip_stack;
+        self.synced_clips = None;
+        self
+    }
+
+    /// The rectangles drawing is clipped to.
+    pub fn clip_stack(&self) -> &ClipStack {
+        &self.clip_stack
+    }
+
+    /// Clip everything drawn from now on to the pixels from `upper_left` up to and including
+    /// `bottom_right`, see [`ClipStack::push`].
+    pub fn push_clip(&mut self, upper_left: BackendCoord, bottom_right: BackendCoord) {
+        self.clip_stack.push(upper_left, bottom_right);
+        self.sync_clips();
+    }
+
+    /// Remove the clip pushed last, see [`ClipStack::pop`].
+    pub fn pop_clip(&mut self) {
+        self.clip_stack.pop();
+        self.sync_clips();
+    }
+
+    /// Collect runs of primitives with the same style into a single path.
+    ///
+    /// Plotters draws every grid line and every point of a scatter plot separately. With
+    /// batching enabled, consecutive lines, rectangles, paths and circles drawn with the same
+    /// style are put into one path which is drawn once the style changes, so the scene
+    /// encodes a single fill or stroke instead of thousands. Polygons, text and bitmaps are
+    /// never batched.
+    ///
+    /// **The last batch is only drawn by `present`.** Dropping the backend or the drawing
+    /// area doesn't draw it, so always call `present` on the drawing area once the plot is
+    /// finished or the last primitives are missing from the scene:
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_vello::VelloBackend;
+    /// # let mut scene = vello::Scene::new();
+    /// let root = VelloBackend::new((400, 300), &mut scene)
+    ///     .with_batching(true)
+    ///     .into_drawing_area();
+    /// root.draw(&Circle::new((200, 150), 50, BLUE.filled()))?;
+    /// root.present()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// Overlapping primitives of the same batch are painted once, so translucent points of a
+    /// scatter plot no longer get darker where they overlap.
+    pub fn with_batching(mut self, batching: bool) -> Self {
+        self.batching = batching;
+        self
     }
 
     #[doc(hidden)]
-    pub fn scene(&mut self) -> &mut Scene {
+    pub fn scene(&mut self) -> &mut C {
         self.scene
     }
+
+    /// Fill `shape`, placed on pixel centers if `mid` is set and on pixel corners otherwise.
+    fn fill(&mut self, color: peniko::Color, mid: bool, shape: &impl kurbo::Shape) {
+        if self.batching {
+            self.batch(BatchStyle::Fill { color, mid }).push(shape);
+        } else {
+            let transform = if mid {
+                self.mid_transform()
+            } else {
+                self.corner_transform()
+            };
+            self.scene.fill(transform, color, shape);
+        }
+    }
+
+    /// Stroke `shape`, placed on pixel centers.
+    fn stroke_shape(
+        &mut self,
+        stroke: kurbo::Stroke,
+        color: peniko::Color,
+        shape: &impl kurbo::Shape,
+    ) {
+        if self.batching {
+            self.batch(BatchStyle::Stroke { color, stroke }).push(shape);
+        } else {
+            self.scene
+                .stroke(&stroke, self.mid_transform(), color, shape);
+        }
+    }
+
+    /// The batch of primitives drawn with `style`, drawing the current batch first if it has
+    /// a different style.
+    fn batch(&mut self, style: BatchStyle) -> &mut Batch {
+        if self
+            .batch
+            .as_ref()
+            .is_some_and(|batch| batch.style != style)
+        {
+            self.flush_batch();
+        }
+        self.batch.get_or_insert_with(|| Batch::new(style))
+    }
+
+    /// Draw the primitives collected in the current batch.
+    fn flush_batch(&mut self) {
+        let Some(batch) = self.batch.take() else {
+            return;
+        };
+        match batch.style {
+            BatchStyle::Fill { color, mid: true } => {
+                self.scene.fill(self.mid_transform(), color, &batch.path)
+            }
+            BatchStyle::Fill { color, mid: false } => {
+                self.scene.fill(self.corner_transform(), color, &batch.path)
+            }
+            BatchStyle::Stroke { color, stroke } => {
+                self.scene
+                    .stroke(&stroke, self.mid_transform(), color, &batch.path)
+            }
+        }
+    }
+
+    /// Push and pop clip layers until the layers of the scene match the clip stack.
+    fn sync_clips(&mut self) {
+        if self.clip_to_size && !self.size_clipped {
+            self.flush_batch();
+            let (width, height) = self.size;
+            let rect = kurbo::Rect::new(0., 0., width as f64, height as f64);
+            self.scene.push_clip(self.transform, &rect);
+            self.size_clipped = true;
+        }
+
+        let generation = self.clip_stack.generation();
+        if self.synced_clips == Some(generation) {
+            return;
+        }
+        let clip_stack = self.clip_stack.clone();
+        let clips = clip_stack.rects();
+        let kept = self
+            .pushed_clips
+            .iter()
+            .zip(clips.iter())
+            .take_while(|(pushed, clip)| pushed == clip)
+            .count();
+        if kept < self.pushed_clips.len().max(clips.len()) {
+            self.flush_batch();
+        }
+        for _ in kept..self.pushed_clips.len() {
+            self.scene.pop_clip();
+        }
+        self.pushed_clips.truncate(kept);
+        for clip in &clips[kept..] {
+            self.scene
+                .push_clip(self.corner_transform(), &clip::clip_rect(*clip));
+            self.pushed_clips.push(*clip);
+        }
+        self.synced_clips = Some(generation);
+    }
+
+    /// The transform into the scene for geometry placed on pixel corners.
+    fn corner_transform(&self) -> kurbo::Affine {
+        self.transform * kurbo::Affine::scale(1. / self.supersampling as f64)
+    }
+
+    /// The transform into the scene for geometry placed on pixel centers with
+    /// [`plotters_point_to_kurbo_mid`], which keeps the centers of whole pixels on the
+    /// centers of scene pixels.
+    fn mid_transform(&self) -> kurbo::Affine {
+        let scale = 1. / self.supersampling as f64;
+        self.transform
+            * kurbo::Affine::scale(scale).then_translate(kurbo::Vec2::new(0.5, 0.5) * (1. - scale))
+    }
+
+    /// The stroke of `primitive` with a width of `width` scene pixels.
+    fn stroke(&self, primitive: StrokedPrimitive, width: u32) -> kurbo::Stroke {
+        let scale = self.supersampling as f64;
+        let mut stroke = self.stroke_styles.stroke(primitive, width as f64 * scale);
+        stroke
+            .dash_pattern
+            .iter_mut()
+            .for_each(|dash| *dash *= scale);
+        stroke.dash_offset *= scale;
+        stroke
+    }
 }
 
-impl DrawingBackend for VelloBackend<'_> {
+impl<C: Canvas> DrawingBackend for VelloBackend<'_, C> {
     type ErrorType = Error;
 
     fn get_size(&self) -> (u32, u32) {
-        self.size
+        (
+            self.size.0 * self.supersampling,
+            self.size.1 * self.supersampling,
+        )
     }
 
     fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.sync_clips();
         Ok(())
     }
 
     fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.flush_batch();
+
+        // Close all clip layers so that whatever is drawn into the scene next isn't clipped,
+        // they are pushed again if the backend keeps drawing
+        for _ in 0..self.pushed_clips.len() + usize::from(self.size_clipped) {
+            self.scene.pop_clip();
+        }
+        self.pushed_clips.clear();
+        self.synced_clips = None;
+        self.size_clipped = false;
         Ok(())
     }
 
@@ -63,13 +421,12 @@ impl DrawingBackend for VelloBackend<'_> {
         point: BackendCoord,
         color: BackendColor,
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        // A pixel of the supersampled grid, the corner transform scales it down into the scene
         let x = point.0 as f64;
         let y = point.1 as f64;
-        self.scene.fill(
-            peniko::Fill::NonZero,
-            kurbo::Affine::IDENTITY,
+        self.fill(
             plotters_color_to_peniko(&color),
-            None,
+            false,
             &kurbo::Rect::new(x, y, x + 1., y + 1.),
         );
         Ok(())
@@ -84,11 +441,9 @@ impl DrawingBackend for VelloBackend<'_> {
         let from = plotters_point_to_kurbo_mid(from);
         let to = plotters_point_to_kurbo_mid(to);
 
-        self.scene.stroke(
-            &kurbo::Stroke::new(style.stroke_width() as f64).with_end_cap(kurbo::Cap::Square),
-            kurbo::Affine::IDENTITY,
+        self.stroke_shape(
+            self.stroke(StrokedPrimitive::Line, style.stroke_width()),
             plotters_color_to_peniko(&style.color()),
-            None,
             &kurbo::Line::new(from, to),
         );
         Ok(())
@@ -108,25 +463,19 @@ impl DrawingBackend for VelloBackend<'_> {
             let mut bottom_right = plotters_point_to_kurbo_corner(bottom_right);
             bottom_right.x += 1.;
             bottom_right.y += 1.;
-            let rect = kurbo::Rect::new(upper_left.x, upper_left.y, bottom_right.x, bottom_right.y);
+            // Normalized so that batched rectangles all wind the same way
+            let rect =
+                kurbo::Rect::new(upper_left.x, upper_left.y, bottom_right.x, bottom_right.y).abs();
 
-            self.scene.fill(
-                peniko::Fill::NonZero,
-                kurbo::Affine::IDENTITY,
-                color,
-                None,
-                &rect,
-            );
+            self.fill(color, false, &rect);
         } else {
             let upper_left = plotters_point_to_kurbo_mid(upper_left);
             let bottom_right = plotters_point_to_kurbo_mid(bottom_right);
             let rect = kurbo::Rect::new(upper_left.x, upper_left.y, bottom_right.x, bottom_right.y);
 
-            self.scene.stroke(
-                &kurbo::Stroke::new(style.stroke_width() as f64).with_end_cap(kurbo::Cap::Square),
-                kurbo::Affine::IDENTITY,
+            self.stroke_shape(
+                self.stroke(StrokedPrimitive::Rect, style.stroke_width()),
                 color,
-                None,
                 &rect,
             );
         }
@@ -145,11 +494,9 @@ impl DrawingBackend for VelloBackend<'_> {
 
         let path: Vec<kurbo::PathEl> = plotters_path_to_kurbo(path).collect();
 
-        self.scene.stroke(
-            &kurbo::Stroke::new(style.stroke_width() as f64).with_end_cap(kurbo::Cap::Square),
-            kurbo::Affine::IDENTITY,
+        self.stroke_shape(
+            self.stroke(StrokedPrimitive::Path, style.stroke_width()),
             plotters_color_to_peniko(&style.color()),
-            None,
             &kurbo::BezPath::from_vec(path),
         );
         Ok(())
@@ -164,22 +511,14 @@ impl DrawingBackend for VelloBackend<'_> {
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
         let center = plotters_point_to_kurbo_mid(center);
         let color = plotters_color_to_peniko(&style.color());
-        let circle = kurbo::Circle::new(center, radius as f64);
+        let circle = kurbo::Circle::new(center, radius as f64 * self.supersampling as f64);
 
         if fill {
-            self.scene.fill(
-                peniko::Fill::NonZero,
-                kurbo::Affine::IDENTITY,
-                color,
-                None,
-                &circle,
-            );
+            self.fill(color, true, &circle);
         } else {
-            self.scene.stroke(
-                &kurbo::Stroke::new(style.stroke_width() as f64).with_end_cap(kurbo::Cap::Square),
-                kurbo::Affine::IDENTITY,
+            self.stroke_shape(
+                self.stroke(StrokedPrimitive::Circle, style.stroke_width()),
                 color,
-                None,
                 &circle,
             );
         }
@@ -198,35 +537,185 @@ impl DrawingBackend for VelloBackend<'_> {
         let path: Vec<kurbo::PathEl> = plotters_path_to_kurbo(vert)
             .chain(std::iter::once(kurbo::PathEl::ClosePath))
             .collect();
+        // Polygons can wind either way, so they would cut holes into each other in a batch
+        self.flush_batch();
         self.scene.fill(
-            peniko::Fill::NonZero,
-            kurbo::Affine::IDENTITY,
+            self.mid_transform(),
             plotters_color_to_peniko(&style.color()),
-            None,
             &kurbo::BezPath::from_vec(path),
         );
 
         Ok(())
     }
 
+    fn draw_text<TStyle: plotters_backend::BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let layout = self
+            .text
+            .get_mut()
+            .layout(text, style, self.supersampling as f64)
+            .map_err(DrawingErrorKind::DrawingError)?;
+        let transform = self.corner_transform() * text::text_transform(&layout, style, pos);
+        if !transform.is_finite() {
+            return Err(DrawingErrorKind::DrawingError(Error::NonFiniteGeometry {
+                primitive: "text",
+            }));
+        }
+
+        // Hinting only makes sense for glyphs that are neither rotated nor scaled
+        let hint = matches!(style.transform(), plotters_backend::FontTransform::None)
+            && self.supersampling == 1;
+        self.flush_batch();
+        text::draw_layout(self.scene, &layout, transform, hint);
+
+        Ok(())
+    }
+
+    fn estimate_text_size<TStyle: plotters_backend::BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+    ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
+        let layout = self
+            .text
+            .borrow_mut()
+            .layout(text, style, self.supersampling as f64)
+            .map_err(DrawingErrorKind::DrawingError)?;
+        Ok(text::text_size(&layout, style))
+    }
+
     fn blit_bitmap(
         &mut self,
         pos: BackendCoord,
         (iw, ih): (u32, u32),
         src: &[u8],
     ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
-        let data = src.to_vec();
+        if iw == 0 || ih == 0 {
+            return Ok(());
+        }
+
+        let data = bitmap::to_rgba8(src, (iw, ih), self.bitmap_format).ok_or(
+            DrawingErrorKind::DrawingError(Error::InvalidBitmap {
+                width: iw,
+                height: ih,
+                len: src.len(),
+                format: self.bitmap_format,
+            }),
+        )?;
         let image = peniko::ImageData {
             data: data.into(),
             format: peniko::ImageFormat::Rgba8,
             alpha_type: peniko::ImageAlphaType::Alpha,
             width: iw,
             height: ih,
-        }
-        .into();
-        let transform = kurbo::Affine::translate((pos.0 as f64, pos.1 as f64));
-        self.scene.draw_image(&image, transform);
-        Ok(())
+        };
+        let transform =
+            self.corner_transform() * kurbo::Affine::translate((pos.0 as f64, pos.1 as f64));
+        self.flush_batch();
+        self.scene
+            .draw_image(&image, transform)
+            .map_err(DrawingErrorKind::DrawingError)
+    }
+}
+
+/// Lets plotters draw with a borrowed backend, so that the owner of the backend can still
+/// present it once plotters is done, like the rendering functions of the `cpu` module do.
+impl<C: Canvas> DrawingBackend for &mut VelloBackend<'_, C> {
+    type ErrorType = Error;
+
+    fn get_size(&self) -> (u32, u32) {
+        (**self).get_size()
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).ensure_prepared()
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).present()
+    }
+
+    fn draw_pixel(
+        &mut self,
+        point: BackendCoord,
+        color: BackendColor,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).draw_pixel(point, color)
+    }
+
+    fn draw_line<S: plotters_backend::BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).draw_line(from, to, style)
+    }
+
+    fn draw_rect<S: plotters_backend::BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).draw_rect(upper_left, bottom_right, style, fill)
+    }
+
+    fn draw_path<S: plotters_backend::BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).draw_path(path, style)
+    }
+
+    fn draw_circle<S: plotters_backend::BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).draw_circle(center, radius, style, fill)
+    }
+
+    fn fill_polygon<S: plotters_backend::BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).fill_polygon(vert, style)
+    }
+
+    fn draw_text<TStyle: plotters_backend::BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).draw_text(text, style, pos)
+    }
+
+    fn estimate_text_size<TStyle: plotters_backend::BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+    ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
+        (**self).estimate_text_size(text, style)
+    }
+
+    fn blit_bitmap(
+        &mut self,
+        pos: BackendCoord,
+        size: (u32, u32),
+        src: &[u8],
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        (**self).blit_bitmap(pos, size, src)
     }
 }
 
@@ -346,10 +835,7 @@ mod tests {
         {
             let mut scene = Scene::new();
 
-            let vello_backend = VelloBackend {
-                size: (width, height),
-                scene: &mut scene,
-            };
+            let vello_backend = VelloBackend::new((width, height), &mut scene);
 
             let root = vello_backend.into_drawing_area();
             root.fill(&WHITE).unwrap();
@@ -405,6 +891,112 @@ mod tests {
         assert_eq!(&result_unpadded, &[255; 6 * 4]);
     }
 
+    #[test]
+    fn draw_text() {
+        let mut scene = Scene::new();
+        let backend = VelloBackend::new((100, 100), &mut scene);
+        let style = ("sans-serif", 20).into_font().color(&BLACK);
+
+        let (width, height) = backend.estimate_text_size("y=x^2", &style).unwrap();
+        assert!(width > 0 && height > 0);
+
+        let rotated = style.transform(FontTransform::Rotate90);
+        assert_eq!(
+            backend.estimate_text_size("y=x^2", &rotated).unwrap(),
+            (height, width)
+        );
+
+        let root = backend.into_drawing_area();
+        root.draw_text("y=x^2", &style, (10, 10)).unwrap();
+        drop(root);
+
+        assert!(!scene.encoding().resources.glyph_runs.is_empty());
+
+        let mut scene = Scene::new();
+        let backend = VelloBackend::new((100, 100), &mut scene);
+        assert!(matches!(
+            backend
+                .estimate_text_size("y=x^2", &("sans-serif", f64::NAN).into_font().color(&BLACK)),
+            Err(DrawingErrorKind::DrawingError(Error::TextLayout { .. }))
+        ));
+    }
+
+    #[test]
+    fn blit_bitmap_formats() {
+        let rgb = [1, 2, 3, 4, 5, 6];
+        let rgba = [1, 2, 3, 255, 4, 5, 6, 255];
+        let bgrx = [3, 2, 1, 0, 6, 5, 4, 0];
+
+        assert_eq!(bitmap::to_rgba8(&rgb, (2, 1), None).unwrap(), rgba);
+        assert_eq!(bitmap::to_rgba8(&rgba, (1, 2), None).unwrap(), rgba);
+        assert_eq!(
+            bitmap::to_rgba8(&bgrx, (2, 1), Some(BitmapFormat::Bgrx)).unwrap(),
+            rgba
+        );
+        assert_eq!(bitmap::to_rgba8(&rgb, (2, 2), None), None);
+        assert_eq!(
+            bitmap::to_rgba8(&rgb, (2, 1), Some(BitmapFormat::Rgba)),
+            None
+        );
+
+        let mut scene = Scene::new();
+        let mut backend = VelloBackend::new((10, 10), &mut scene);
+        assert!(matches!(
+            backend.blit_bitmap((0, 0), (3, 3), &rgb),
+            Err(DrawingErrorKind::DrawingError(Error::InvalidBitmap {
+                width: 3,
+                height: 3,
+                len: 6,
+                format: None,
+            }))
+        ));
+        assert!(backend.blit_bitmap((0, 0), (2, 1), &rgb).is_ok());
+    }
+
+    #[test]
+    fn supersampling() {
+        let mut scene = Scene::new();
+        let backend = VelloBackend::new((100, 50), &mut scene).with_supersampling(4);
+        assert_eq!(backend.get_size(), (400, 200));
+
+        let root = backend.into_drawing_area();
+        root.draw(&PathElement::new([(0, 0), (399, 199)], RED.stroke_width(2)))
+            .unwrap();
+        drop(root);
+
+        let encoding = scene.encoding();
+        assert!(encoding.transforms.iter().any(|transform| {
+            transform.matrix == [0.25, 0., 0., 0.25] && transform.translation == [0.375, 0.375]
+        }));
+        assert!(encoding.styles.iter().any(|style| style.line_width == 8.));
+
+        // Pixels are pixels of the finer grid, placed by the transform of the backend
+        let mut scene = Scene::new();
+        let backend = VelloBackend::new((100, 50), &mut scene)
+            .with_supersampling(4)
+            .with_transform(kurbo::Affine::translate((10., 20.)));
+        let root = backend.into_drawing_area();
+        root.draw_pixel((399, 199), &RED).unwrap();
+        drop(root);
+
+        let encoding = scene.encoding();
+        assert_eq!(encoding.n_paths, 1);
+        assert!(encoding.transforms.iter().all(|transform| {
+            transform.matrix == [0.25, 0., 0., 0.25] && transform.translation == [10., 20.]
+        }));
+        let coords: Vec<f32> = encoding
+            .path_data
+            .iter()
+            .map(|bits| f32::from_bits(*bits))
+            .collect();
+        assert!(!coords.is_empty());
+        assert!(
+            coords
+                .iter()
+                .all(|&c| [199., 200., 399., 400.].contains(&c))
+        );
+    }
+
     #[test]
     fn test_plotters_path_to_kurbo() {
         let path = vec![(1, 2), (3, 4), (5, 6)];
@@ -0,0 +1,951 @@
+use std::cell::RefCell;
+use std::fmt;
+
+use plotters_backend::text_anchor::{HPos, Pos, VPos};
+use plotters_backend::{
+    BackendColor, BackendCoord, BackendStyle, BackendTextStyle, DrawingBackend, DrawingErrorKind,
+    FontFamily, FontStyle, FontTransform,
+};
+
+use crate::text::{self, TextContext};
+use crate::{
+    BitmapFormat, Canvas, Error, StrokeStyles, StrokedPrimitive, VelloBackend, VelloStrokeStyle,
+    bitmap,
+};
+
+/// The primitives of a plot, recorded by a [`RecordingBackend`].
+///
+/// A display list can be rendered into a Vello scene with [`DisplayList::render`], replayed
+/// onto any other backend with [`DisplayList::replay`] or, with the `svg` feature, turned
+/// into an SVG document with `DisplayList::to_svg`, so the same plotting code can draw on
+/// screen and produce vector output:
+///
+/// ```rust
+/// # use plotters::prelude::*;
+/// # use plotters_vello::{DisplayList, RecordingBackend, VelloBackend};
+/// let mut list = DisplayList::new((400, 300));
+/// let root = RecordingBackend::new(&mut list).into_drawing_area();
+/// root.fill(&WHITE)?;
+/// root.draw(&Circle::new((200, 150), 50, RED.filled()))?;
+/// drop(root);
+///
+/// let mut scene = vello::Scene::new();
+/// list.render(&mut VelloBackend::new((400, 300), &mut scene))?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// Display lists only hold plain data, so a plot can be recorded on a worker thread and be
+/// drawn cheaply later:
+///
+/// ```rust
+/// # use plotters::prelude::*;
+/// # use plotters_vello::{DisplayList, RecordingBackend, VelloBackend};
+/// let list = std::thread::spawn(|| {
+///     let mut list = DisplayList::new((400, 300));
+///     let root = RecordingBackend::new(&mut list).into_drawing_area();
+///     root.fill(&WHITE)?;
+///     drop(root);
+///     Ok::<_, Box<dyn std::error::Error + Send + Sync>>(list)
+/// })
+/// .join()
+/// .unwrap()?;
+///
+/// let mut scene = vello::Scene::new();
+/// let mut backend = VelloBackend::new((1000, 700), &mut scene);
+/// // Draw the plot at twice its size, 100 pixels from the top left corner
+/// list.replay(&mut backend, (100, 100), 2.)?;
+/// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayList {
+    size: (u32, u32),
+    commands: Vec<DrawCommand>,
+}
+
+impl DisplayList {
+    /// An empty display list for a plot of the given size in pixels.
+    pub fn new(size: (u32, u32)) -> Self {
+        Self {
+            size,
+            commands: Vec::new(),
+        }
+    }
+
+    /// The size of the plot in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// The recorded primitives, in the order they were drawn.
+    ///
+    /// Tests can compare these with the primitives they expect instead of rendering pixels.
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Remove all recorded primitives.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Draw the recorded primitives with `backend`.
+    ///
+    /// Outlines are stroked with the recorded stroke styles, the styles of `backend` are left
+    /// unchanged. The primitives are clipped by the [`ClipStack`](crate::ClipStack) of
+    /// `backend` like everything drawn with plotters.
+    ///
+    /// The pixels of the display list are the pixels of the scene, placed with the
+    /// [transform](VelloBackend::with_transform) of `backend`. If `backend` is
+    /// [supersampled](VelloBackend::with_supersampling), the primitives are still drawn on
+    /// the pixels of the scene rather than on the finer grid.
+    pub fn render<C: Canvas>(
+        &self,
+        backend: &mut VelloBackend<'_, C>,
+    ) -> Result<(), DrawingErrorKind<Error>> {
+        // Push the clips and draw the pending batch while they are still placed on the finer
+        // grid, then draw the recorded primitives without supersampling
+        backend.ensure_prepared()?;
+        backend.flush_batch();
+        let supersampling = std::mem::replace(&mut backend.supersampling, 1);
+        let bitmap_format = backend.bitmap_format.replace(BitmapFormat::Rgba);
+        let styles = backend.stroke_styles().clone();
+        let result = self.commands.iter().try_for_each(|command| {
+            let Some((primitive, stroke)) = command.stroke() else {
+                return command.replay(backend, &Placement::IDENTITY, true);
+            };
+            let previous = styles.replace(primitive, Some(stroke.clone()));
+            let result = command.replay(backend, &Placement::IDENTITY, true);
+            styles.replace(primitive, previous);
+            result
+        });
+        backend.flush_batch();
+        backend.bitmap_format = bitmap_format;
+        backend.supersampling = supersampling;
+        result
+    }
+
+    /// Draw the recorded primitives with any plotters backend, scaled by `scale` and then
+    /// moved by `offset` pixels.
+    ///
+    /// Coordinates, stroke widths, radii, font sizes and bitmaps are all scaled. Plotters
+    /// backends only know the color and width of strokes, so outlines are drawn with the
+    /// stroke styles of `backend`, use [`DisplayList::render`] to keep the recorded ones.
+    /// Bitmaps are passed on as RGB like plotters does, dropping their alpha channel, and
+    /// text can only be replayed onto backends that lay out text themselves, like
+    /// [`VelloBackend`] or plotters' `SVGBackend`.
+    pub fn replay<DB: DrawingBackend>(
+        &self,
+        backend: &mut DB,
+        offset: BackendCoord,
+        scale: f64,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        let placement = Placement { offset, scale };
+        self.commands
+            .iter()
+            .try_for_each(|command| command.replay(backend, &placement, false))
+    }
+}
+
+/// How replayed primitives are placed on the target backend.
+struct Placement {
+    offset: BackendCoord,
+    scale: f64,
+}
+
+impl Placement {
+    const IDENTITY: Placement = Placement {
+        offset: (0, 0),
+        scale: 1.,
+    };
+
+    fn point(&self, (x, y): BackendCoord) -> BackendCoord {
+        (
+            self.offset.0 + (x as f64 * self.scale).round() as i32,
+            self.offset.1 + (y as f64 * self.scale).round() as i32,
+        )
+    }
+
+    fn length(&self, length: u32) -> u32 {
+        (length as f64 * self.scale).round() as u32
+    }
+
+    fn style(&self, style: &RecordedStyle) -> RecordedStyle {
+        RecordedStyle {
+            // Keep hairlines visible when scaling down
+            stroke_width: self
+                .length(style.stroke_width)
+                .max(style.stroke_width.min(1)),
+            ..*style
+        }
+    }
+
+    /// Scales an RGBA bitmap with nearest neighbor sampling and converts it to RGB or keeps
+    /// it as RGBA.
+    fn bitmap(&self, (width, height): (u32, u32), rgba: &[u8], keep_alpha: bool) -> Vec<u8> {
+        let size = (self.length(width), self.length(height));
+        let channels = if keep_alpha { 4 } else { 3 };
+        let mut scaled = Vec::with_capacity(size.0 as usize * size.1 as usize * channels);
+        for y in 0..size.1 {
+            let src_y = ((y as f64 / self.scale) as u32).min(height - 1);
+            for x in 0..size.0 {
+                let src_x = ((x as f64 / self.scale) as u32).min(width - 1);
+                let i = (src_y * width + src_x) as usize * 4;
+                scaled.extend_from_slice(&rgba[i..i + channels]);
+            }
+        }
+        scaled
+    }
+}
+
+/// A single primitive drawn by plotters, with its style resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawCommand {
+    /// A single pixel, drawn with `draw_pixel`.
+    Pixel {
+        /// The pixel that is filled.
+        point: BackendCoord,
+        /// The color of the pixel, its stroke width is unused.
+        style: RecordedStyle,
+    },
+    /// A straight line between the centers of two pixels.
+    Line {
+        /// The pixel the line starts at.
+        from: BackendCoord,
+        /// The pixel the line ends at.
+        to: BackendCoord,
+        /// The color and width of the line.
+        style: RecordedStyle,
+        /// The joins, caps and dashes of the line.
+        stroke: VelloStrokeStyle,
+    },
+    /// A rectangle, `stroke` is only used if it isn't filled.
+    Rect {
+        /// One corner pixel of the rectangle, plotters doesn't order the corners.
+        upper_left: BackendCoord,
+        /// The opposite corner pixel, which is part of the rectangle.
+        bottom_right: BackendCoord,
+        /// The color of the rectangle and the width of its outline.
+        style: RecordedStyle,
+        /// Whether the rectangle is filled instead of outlined.
+        fill: bool,
+        /// The joins, caps and dashes of the outline.
+        stroke: VelloStrokeStyle,
+    },
+    /// A line through the centers of several pixels, e.g. a line series.
+    Path {
+        /// The pixels the path passes through, in order.
+        points: Vec<BackendCoord>,
+        /// The color and width of the path.
+        style: RecordedStyle,
+        /// The joins, caps and dashes of the path.
+        stroke: VelloStrokeStyle,
+    },
+    /// A circle, `stroke` is only used if it isn't filled.
+    Circle {
+        /// The pixel at the center of the circle.
+        center: BackendCoord,
+        /// The radius in pixels.
+        radius: u32,
+        /// The color of the circle and the width of its outline.
+        style: RecordedStyle,
+        /// Whether the circle is filled instead of outlined.
+        fill: bool,
+        /// The joins, caps and dashes of the outline.
+        stroke: VelloStrokeStyle,
+    },
+    /// A filled polygon.
+    Polygon {
+        /// The pixels at the corners of the polygon, in order.
+        points: Vec<BackendCoord>,
+        /// The color of the polygon, its stroke width is unused.
+        style: RecordedStyle,
+    },
+    /// A single line of text.
+    Text {
+        /// The text that is drawn.
+        text: String,
+        /// The font, color, rotation and anchor of the text.
+        style: RecordedTextStyle,
+        /// The position of the anchor of the text.
+        pos: BackendCoord,
+    },
+    /// A bitmap with its pixels converted to RGBA.
+    Bitmap {
+        /// The pixel the top left corner of the bitmap is drawn at.
+        pos: BackendCoord,
+        /// The width and height of the bitmap in pixels.
+        size: (u32, u32),
+        /// The pixels of the bitmap, row by row with 4 bytes each.
+        rgba: Vec<u8>,
+    },
+}
+
+impl DrawCommand {
+    /// The kind of primitive and stroke style of an outline.
+    fn stroke(&self) -> Option<(StrokedPrimitive, &VelloStrokeStyle)> {
+        match self {
+            DrawCommand::Line { stroke, .. } => Some((StrokedPrimitive::Line, stroke)),
+            DrawCommand::Rect {
+                fill: false,
+                stroke,
+                ..
+            } => Some((StrokedPrimitive::Rect, stroke)),
+            DrawCommand::Path { stroke, .. } => Some((StrokedPrimitive::Path, stroke)),
+            DrawCommand::Circle {
+                fill: false,
+                stroke,
+                ..
+            } => Some((StrokedPrimitive::Circle, stroke)),
+            _ => None,
+        }
+    }
+
+    /// Draw the command with `backend`, passing bitmaps on as RGBA if `keep_alpha` is set and
+    /// as RGB otherwise.
+    fn replay<DB: DrawingBackend>(
+        &self,
+        backend: &mut DB,
+        placement: &Placement,
+        keep_alpha: bool,
+    ) -> Result<(), DrawingErrorKind<DB::ErrorType>> {
+        // Plotters prepares the backend before every primitive, e.g. to apply its clips
+        backend.ensure_prepared()?;
+        match self {
+            DrawCommand::Pixel { point, style } => {
+                let size = placement.length(1);
+                if size <= 1 {
+                    backend.draw_pixel(placement.point(*point), style.color)
+                } else {
+                    let (x, y) = placement.point(*point);
+                    let bottom_right = (x + size as i32 - 1, y + size as i32 - 1);
+                    backend.draw_rect((x, y), bottom_right, style, true)
+                }
+            }
+            DrawCommand::Line {
+                from, to, style, ..
+            } => backend.draw_line(
+                placement.point(*from),
+                placement.point(*to),
+                &placement.style(style),
+            ),
+            DrawCommand::Rect {
+                upper_left,
+                bottom_right,
+                style,
+                fill,
+                ..
+            } => {
+                let bottom_right = if *fill {
+                    // Filled rectangles include their bottom right pixel
+                    let (x, y) = placement.point((bottom_right.0 + 1, bottom_right.1 + 1));
+                    (x - 1, y - 1)
+                } else {
+                    placement.point(*bottom_right)
+                };
+                backend.draw_rect(
+                    placement.point(*upper_left),
+                    bottom_right,
+                    &placement.style(style),
+                    *fill,
+                )
+            }
+            DrawCommand::Path { points, style, .. } => backend.draw_path(
+                points.iter().map(|point| placement.point(*point)),
+                &placement.style(style),
+            ),
+            DrawCommand::Circle {
+                center,
+                radius,
+                style,
+                fill,
+                ..
+            } => backend.draw_circle(
+                placement.point(*center),
+                placement.length(*radius),
+                &placement.style(style),
+                *fill,
+            ),
+            DrawCommand::Polygon { points, style } => {
+                backend.fill_polygon(points.iter().map(|point| placement.point(*point)), style)
+            }
+            DrawCommand::Text { text, style, pos } => {
+                let style = RecordedTextStyle {
+                    size: style.size * placement.scale,
+                    ..style.clone()
+                };
+                backend.draw_text(text, &style, placement.point(*pos))
+            }
+            DrawCommand::Bitmap { pos, size, rgba } => {
+                let scaled = (placement.length(size.0), placement.length(size.1));
+                if scaled.0 == 0 || scaled.1 == 0 {
+                    return Ok(());
+                }
+                let pixels = placement.bitmap(*size, rgba, keep_alpha);
+                backend.blit_bitmap(placement.point(*pos), scaled, &pixels)
+            }
+        }
+    }
+}
+
+/// The color and stroke width of a recorded primitive.
+#[derive(Clone, Copy)]
+pub struct RecordedStyle {
+    /// The color of the primitive.
+    pub color: BackendColor,
+    /// The width of outlines in pixels.
+    pub stroke_width: u32,
+}
+
+impl RecordedStyle {
+    fn new<S: BackendStyle>(style: &S) -> Self {
+        Self {
+            color: style.color(),
+            stroke_width: style.stroke_width(),
+        }
+    }
+}
+
+impl BackendStyle for RecordedStyle {
+    fn color(&self) -> BackendColor {
+        self.color
+    }
+
+    fn stroke_width(&self) -> u32 {
+        self.stroke_width
+    }
+}
+
+impl fmt::Debug for RecordedStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordedStyle")
+            .field("rgb", &self.color.rgb)
+            .field("alpha", &self.color.alpha)
+            .field("stroke_width", &self.stroke_width)
+            .finish()
+    }
+}
+
+impl PartialEq for RecordedStyle {
+    fn eq(&self, other: &Self) -> bool {
+        self.color.rgb == other.color.rgb
+            && self.color.alpha == other.color.alpha
+            && self.stroke_width == other.stroke_width
+    }
+}
+
+/// The font of a recorded piece of text.
+#[derive(Clone)]
+pub struct RecordedTextStyle {
+    /// The color of the text.
+    pub color: BackendColor,
+    /// The font size in pixels.
+    pub size: f64,
+    /// The font family, as used by the `font-family` property of CSS.
+    pub family: String,
+    /// Whether the font is bold or italic.
+    pub style: FontStyle,
+    /// The rotation of the text, see [`RecordedTextStyle::rotation`].
+    pub transform: FontTransform,
+    /// Which point of the text is placed at the position it is drawn at.
+    pub anchor: Pos,
+}
+
+impl RecordedTextStyle {
+    fn new<S: BackendTextStyle>(style: &S) -> Self {
+        Self {
+            color: style.color(),
+            size: style.size(),
+            family: style.family().as_str().to_owned(),
+            style: style.style(),
+            transform: style.transform(),
+            anchor: style.anchor(),
+        }
+    }
+
+    /// The clockwise rotation of the text in degrees.
+    pub fn rotation(&self) -> u32 {
+        match self.transform {
+            FontTransform::None => 0,
+            FontTransform::Rotate90 => 90,
+            FontTransform::Rotate180 => 180,
+            FontTransform::Rotate270 => 270,
+        }
+    }
+}
+
+impl BackendTextStyle for RecordedTextStyle {
+    type FontError = Error;
+
+    fn color(&self) -> BackendColor {
+        self.color
+    }
+
+    fn size(&self) -> f64 {
+        self.size
+    }
+
+    fn transform(&self) -> FontTransform {
+        self.transform.clone()
+    }
+
+    fn style(&self) -> FontStyle {
+        self.style
+    }
+
+    fn anchor(&self) -> Pos {
+        self.anchor
+    }
+
+    fn family(&self) -> FontFamily<'_> {
+        FontFamily::from(self.family.as_str())
+    }
+
+    /// Recorded text styles have no font of their own, so only backends that lay out text
+    /// themselves can draw them.
+    fn layout_box(&self, text: &str) -> Result<((i32, i32), (i32, i32)), Self::FontError> {
+        Err(Error::TextLayout {
+            text: text.to_owned(),
+            reason: "recorded text styles can't be laid out without a font".to_owned(),
+        })
+    }
+
+    fn draw<E, DrawFunc: FnMut(i32, i32, BackendColor) -> Result<(), E>>(
+        &self,
+        text: &str,
+        _pos: BackendCoord,
+        _draw: DrawFunc,
+    ) -> Result<Result<(), E>, Self::FontError> {
+        self.layout_box(text).map(|_| Ok(()))
+    }
+}
+
+impl fmt::Debug for RecordedTextStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordedTextStyle")
+            .field("rgb", &self.color.rgb)
+            .field("alpha", &self.color.alpha)
+            .field("size", &self.size)
+            .field("family", &self.family)
+            .field("style", &self.style.as_str())
+            .field("rotation", &self.rotation())
+            .field("anchor", &anchor_name(self.anchor))
+            .finish()
+    }
+}
+
+impl PartialEq for RecordedTextStyle {
+    fn eq(&self, other: &Self) -> bool {
+        self.color.rgb == other.color.rgb
+            && self.color.alpha == other.color.alpha
+            && self.size == other.size
+            && self.family == other.family
+            && self.style.as_str() == other.style.as_str()
+            && self.rotation() == other.rotation()
+            && anchor_name(self.anchor) == anchor_name(other.anchor)
+    }
+}
+
+/// The horizontal and vertical position of an anchor, named like in CSS.
+pub(crate) fn anchor_name(anchor: Pos) -> (&'static str, &'static str) {
+    (
+        match anchor.h_pos {
+            HPos::Left => "start",
+            HPos::Center => "middle",
+            HPos::Right => "end",
+        },
+        match anchor.v_pos {
+            VPos::Top => "text-before-edge",
+            VPos::Center => "middle",
+            VPos::Bottom => "text-after-edge",
+        },
+    )
+}
+
+/// A backend recording the primitives plotters draws into a [`DisplayList`].
+///
+/// Text is measured with [parley](https://crates.io/crates/parley) like in
+/// [`VelloBackend`], so recorded plots are laid out the same way as plots drawn directly.
+pub struct RecordingBackend<'a> {
+    list: &'a mut DisplayList,
+    text: RefCell<TextContext<'a>>,
+    bitmap_format: Option<BitmapFormat>,
+    stroke_styles: StrokeStyles,
+}
+
+impl fmt::Debug for RecordingBackend<'_> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("RecordingBackend")
+            .field("size", &self.list.size)
+            .finish()
+    }
+}
+
+impl<'a> RecordingBackend<'a> {
+    /// Create a backend that appends to `list`.
+    pub fn new(list: &'a mut DisplayList) -> Self {
+        Self {
+            list,
+            text: RefCell::new(TextContext::new()),
+            bitmap_format: None,
+            stroke_styles: StrokeStyles::new(),
+        }
+    }
+
+    /// Create a backend that measures text using the fonts of `font_ctx`.
+    pub fn with_font_context(
+        list: &'a mut DisplayList,
+        font_ctx: &'a mut parley::FontContext,
+    ) -> Self {
+        Self {
+            list,
+            text: RefCell::new(TextContext::with_font_context(font_ctx)),
+            bitmap_format: None,
+            stroke_styles: StrokeStyles::new(),
+        }
+    }
+
+    /// Set the pixel layout of bitmaps, see [`VelloBackend::with_bitmap_format`].
+    pub fn with_bitmap_format(mut self, format: BitmapFormat) -> Self {
+        self.bitmap_format = Some(format);
+        self
+    }
+
+    /// Record outlines with `styles`, see [`VelloBackend::with_stroke_styles`].
+    pub fn with_stroke_styles(mut self, styles: StrokeStyles) -> Self {
+        self.stroke_styles = styles;
+        self
+    }
+
+    /// The styles outlines are recorded with.
+    pub fn stroke_styles(&self) -> &StrokeStyles {
+        &self.stroke_styles
+    }
+
+    fn push(&mut self, command: DrawCommand) -> Result<(), DrawingErrorKind<Error>> {
+        self.list.commands.push(command);
+        Ok(())
+    }
+}
+
+impl DrawingBackend for RecordingBackend<'_> {
+    type ErrorType = Error;
+
+    fn get_size(&self) -> (u32, u32) {
+        self.list.size
+    }
+
+    fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        Ok(())
+    }
+
+    fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        Ok(())
+    }
+
+    fn draw_pixel(
+        &mut self,
+        point: BackendCoord,
+        color: BackendColor,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.push(DrawCommand::Pixel {
+            point,
+            style: RecordedStyle::new(&color),
+        })
+    }
+
+    fn draw_line<S: BackendStyle>(
+        &mut self,
+        from: BackendCoord,
+        to: BackendCoord,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let stroke = self.stroke_styles.get(StrokedPrimitive::Line);
+        self.push(DrawCommand::Line {
+            from,
+            to,
+            style: RecordedStyle::new(style),
+            stroke,
+        })
+    }
+
+    fn draw_rect<S: BackendStyle>(
+        &mut self,
+        upper_left: BackendCoord,
+        bottom_right: BackendCoord,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let stroke = self.stroke_styles.get(StrokedPrimitive::Rect);
+        self.push(DrawCommand::Rect {
+            upper_left,
+            bottom_right,
+            style: RecordedStyle::new(style),
+            fill,
+            stroke,
+        })
+    }
+
+    fn draw_path<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        path: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let stroke = self.stroke_styles.get(StrokedPrimitive::Path);
+        self.push(DrawCommand::Path {
+            points: path.into_iter().collect(),
+            style: RecordedStyle::new(style),
+            stroke,
+        })
+    }
+
+    fn draw_circle<S: BackendStyle>(
+        &mut self,
+        center: BackendCoord,
+        radius: u32,
+        style: &S,
+        fill: bool,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        let stroke = self.stroke_styles.get(StrokedPrimitive::Circle);
+        self.push(DrawCommand::Circle {
+            center,
+            radius,
+            style: RecordedStyle::new(style),
+            fill,
+            stroke,
+        })
+    }
+
+    fn fill_polygon<S: BackendStyle, I: IntoIterator<Item = BackendCoord>>(
+        &mut self,
+        vert: I,
+        style: &S,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        self.push(DrawCommand::Polygon {
+            points: vert.into_iter().collect(),
+            style: RecordedStyle::new(style),
+        })
+    }
+
+    fn draw_text<TStyle: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &TStyle,
+        pos: BackendCoord,
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        // Fail on text that VelloBackend couldn't draw either
+        self.text
+            .get_mut()
+            .layout(text, style, 1.)
+            .map_err(DrawingErrorKind::DrawingError)?;
+
+        self.push(DrawCommand::Text {
+            text: text.to_owned(),
+            style: RecordedTextStyle::new(style),
+            pos,
+        })
+    }
+
+    fn estimate_text_size<TStyle: BackendTextStyle>(
+        &self,
+        text: &str,
+        style: &TStyle,
+    ) -> Result<(u32, u32), DrawingErrorKind<Self::ErrorType>> {
+        let layout = self
+            .text
+            .borrow_mut()
+            .layout(text, style, 1.)
+            .map_err(DrawingErrorKind::DrawingError)?;
+        Ok(text::text_size(&layout, style))
+    }
+
+    fn blit_bitmap(
+        &mut self,
+        pos: BackendCoord,
+        (iw, ih): (u32, u32),
+        src: &[u8],
+    ) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+        if iw == 0 || ih == 0 {
+            return Ok(());
+        }
+
+        let rgba = bitmap::to_rgba8(src, (iw, ih), self.bitmap_format).ok_or(
+            DrawingErrorKind::DrawingError(Error::InvalidBitmap {
+                width: iw,
+                height: ih,
+                len: src.len(),
+                format: self.bitmap_format,
+            }),
+        )?;
+        self.push(DrawCommand::Bitmap {
+            pos,
+            size: (iw, ih),
+            rgba,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plotters::prelude::*;
+    use vello::Scene;
+    use vello::kurbo::Affine;
+
+    use super::{DisplayList, DrawCommand, RecordedStyle, RecordingBackend};
+    use crate::{ClipStack, StrokeStyles, StrokedPrimitive, VelloBackend, VelloStrokeStyle};
+
+    #[test]
+    fn record_and_render() {
+        let mut list = DisplayList::new((100, 100));
+        let styles = StrokeStyles::new();
+        let dashed = VelloStrokeStyle::new().with_dashes(0., [4., 2.]);
+        {
+            let root = RecordingBackend::new(&mut list)
+                .with_stroke_styles(styles.clone())
+                .into_drawing_area();
+            styles.set(StrokedPrimitive::Path, dashed.clone());
+            root.draw(&PathElement::new(
+                [(10, 10), (90, 90)],
+                BLUE.stroke_width(2),
+            ))
+            .unwrap();
+            styles.reset(StrokedPrimitive::Path);
+        }
+        RecordingBackend::new(&mut list)
+            .blit_bitmap((0, 0), (1, 1), &[1, 2, 3])
+            .unwrap();
+
+        assert_eq!(
+            list.commands(),
+            [
+                DrawCommand::Path {
+                    points: vec![(10, 10), (90, 90)],
+                    style: RecordedStyle {
+                        color: BLUE.to_backend_color(),
+                        stroke_width: 2,
+                    },
+                    stroke: dashed,
+                },
+                DrawCommand::Bitmap {
+                    pos: (0, 0),
+                    size: (1, 1),
+                    rgba: vec![1, 2, 3, 255],
+                },
+            ]
+        );
+
+        let mut scene = Scene::new();
+        let mut backend = VelloBackend::new((100, 100), &mut scene);
+        list.render(&mut backend).unwrap();
+        assert_eq!(
+            backend.stroke_styles().get(StrokedPrimitive::Path),
+            VelloStrokeStyle::new()
+        );
+        drop(backend);
+        assert!(!scene.encoding().is_empty());
+    }
+
+    #[test]
+    fn render_supersampled() {
+        let mut list = DisplayList::new((100, 100));
+        RecordingBackend::new(&mut list)
+            .draw_rect((10, 10), (19, 19), &RED, true)
+            .unwrap();
+
+        let mut scene = Scene::new();
+        let mut backend = VelloBackend::new((100, 100), &mut scene)
+            .with_supersampling(4)
+            .with_transform(Affine::translate((50., 50.)))
+            .with_batching(true);
+        list.render(&mut backend).unwrap();
+        assert_eq!(backend.supersampling(), 4);
+        drop(backend);
+
+        // The recorded pixels are pixels of the scene, moved by the transform
+        let encoding = scene.encoding();
+        assert_eq!(encoding.n_paths, 1);
+        assert!(encoding.transforms.iter().all(|transform| {
+            transform.matrix == [1., 0., 0., 1.] && transform.translation == [50., 50.]
+        }));
+    }
+
+    #[test]
+    fn render_clipped() {
+        let mut list = DisplayList::new((100, 100));
+        RecordingBackend::new(&mut list)
+            .into_drawing_area()
+            .fill(&WHITE)
+            .unwrap();
+
+        let mut scene = Scene::new();
+        let clips = ClipStack::new();
+        let mut backend = VelloBackend::new((100, 100), &mut scene).with_clip_stack(clips.clone());
+        clips.push((10, 10), (20, 20));
+        list.render(&mut backend).unwrap();
+        backend.present().unwrap();
+        drop(backend);
+
+        let encoding = scene.encoding();
+        assert_eq!(encoding.n_clips, 2);
+        assert_eq!(encoding.n_open_clips, 0);
+    }
+
+    #[test]
+    fn replay_scaled() {
+        fn assert_send<T: Send>() {}
+        assert_send::<DisplayList>();
+
+        let mut list = DisplayList::new((100, 100));
+        {
+            let root = RecordingBackend::new(&mut list).into_drawing_area();
+            root.draw(&Rectangle::new([(0, 0), (9, 4)], RED.filled()))
+                .unwrap();
+            root.draw(&Circle::new((5, 5), 3, BLACK.stroke_width(1)))
+                .unwrap();
+        }
+        RecordingBackend::new(&mut list)
+            .blit_bitmap((0, 0), (2, 1), &[1, 2, 3, 4, 5, 6])
+            .unwrap();
+
+        let mut replayed = DisplayList::new((300, 300));
+        list.replay(&mut RecordingBackend::new(&mut replayed), (10, 20), 2.)
+            .unwrap();
+
+        let red = RecordedStyle {
+            color: RED.to_backend_color(),
+            stroke_width: 2,
+        };
+        let black = RecordedStyle {
+            color: BLACK.to_backend_color(),
+            stroke_width: 2,
+        };
+        assert_eq!(
+            replayed.commands(),
+            [
+                DrawCommand::Rect {
+                    upper_left: (10, 20),
+                    bottom_right: (29, 29),
+                    style: red,
+                    fill: true,
+                    stroke: VelloStrokeStyle::new(),
+                },
+                DrawCommand::Circle {
+                    center: (20, 30),
+                    radius: 6,
+                    style: black,
+                    fill: false,
+                    stroke: VelloStrokeStyle::new(),
+                },
+                DrawCommand::Bitmap {
+                    pos: (10, 20),
+                    size: (4, 2),
+                    rgba: [[1, 2, 3, 255]; 2]
+                        .into_iter()
+                        .chain([[4, 5, 6, 255]; 2])
+                        .flatten()
+                        .collect::<Vec<_>>()
+                        .repeat(2),
+                },
+            ]
+        );
+    }
+}
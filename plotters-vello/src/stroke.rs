@@ -0,0 +1,211 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use vello::kurbo::{Cap, Join, Stroke};
+
+/// How the outlines drawn by [`VelloBackend`](crate::VelloBackend) are stroked.
+///
+/// Plotters styles only have a color and a width, the rest of the stroke comes from the
+/// [`StrokeStyles`] of the backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VelloStrokeStyle {
+    /// How segments of a path are joined.
+    pub join: Join,
+    /// The limit for miter joins, as a multiple of the stroke width.
+    pub miter_limit: f64,
+    /// The cap at the start of each line and dash.
+    pub start_cap: Cap,
+    /// The cap at the end of each line and dash.
+    pub end_cap: Cap,
+    /// Alternating lengths of dashes and gaps in pixels, or empty for a solid line.
+    pub dash_pattern: Vec<f64>,
+    /// How far into `dash_pattern` the stroke starts, in pixels.
+    pub dash_offset: f64,
+}
+
+impl Default for VelloStrokeStyle {
+    fn default() -> Self {
+        Self {
+            join: Join::Round,
+            miter_limit: 4.,
+            start_cap: Cap::Round,
+            end_cap: Cap::Square,
+            dash_pattern: Vec::new(),
+            dash_offset: 0.,
+        }
+    }
+}
+
+impl VelloStrokeStyle {
+    /// A solid stroke with round joins, a round start cap and a square end cap.
+    ///
+    /// This is the style all primitives are stroked with by default.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Join segments with `join`.
+    pub fn with_join(mut self, join: Join) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Use `cap` at both ends of lines and dashes.
+    pub fn with_caps(mut self, cap: Cap) -> Self {
+        self.start_cap = cap;
+        self.end_cap = cap;
+        self
+    }
+
+    /// Draw dashes following `pattern`, which alternates between the lengths of dashes and
+    /// gaps in pixels, starting `offset` pixels into the pattern.
+    ///
+    /// ```rust
+    /// # use plotters_vello::VelloStrokeStyle;
+    /// // 6 pixel dashes with 4 pixel gaps
+    /// let dashed = VelloStrokeStyle::new().with_dashes(0., [6., 4.]);
+    /// ```
+    pub fn with_dashes(mut self, offset: f64, pattern: impl IntoIterator<Item = f64>) -> Self {
+        self.dash_offset = offset;
+        self.dash_pattern = pattern.into_iter().collect();
+        self
+    }
+
+    /// The kurbo stroke with this style and the given width.
+    pub fn to_stroke(&self, width: f64) -> Stroke {
+        Stroke {
+            width,
+            join: self.join,
+            miter_limit: self.miter_limit,
+            start_cap: self.start_cap,
+            end_cap: self.end_cap,
+            dash_pattern: self.dash_pattern.iter().copied().collect(),
+            dash_offset: self.dash_offset,
+        }
+    }
+}
+
+/// The kinds of primitives [`VelloBackend`](crate::VelloBackend) strokes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StrokedPrimitive {
+    /// Lines drawn with `draw_line`, e.g. grid lines and ticks.
+    Line,
+    /// Outlines of rectangles.
+    Rect,
+    /// Paths drawn with `draw_path`, e.g. line series.
+    Path,
+    /// Outlines of circles.
+    Circle,
+}
+
+/// The stroke styles used by a [`VelloBackend`](crate::VelloBackend) for each kind of
+/// primitive.
+///
+/// This is a handle to styles that can be shared. Keep a clone of the handle passed to
+/// [`VelloBackend::with_stroke_styles`](crate::VelloBackend::with_stroke_styles) to change
+/// the styles while drawing, e.g. to draw a single dashed line:
+///
+/// ```rust
+/// # use plotters::prelude::*;
+/// # use plotters_vello::{StrokeStyles, StrokedPrimitive, VelloBackend, VelloStrokeStyle};
+/// # let mut scene = vello::Scene::new();
+/// let styles = StrokeStyles::new();
+/// let root = VelloBackend::new((400, 300), &mut scene)
+///     .with_stroke_styles(styles.clone())
+///     .into_drawing_area();
+///
+/// let dashed = VelloStrokeStyle::new().with_dashes(0., [6., 4.]);
+/// styles.set(StrokedPrimitive::Path, dashed);
+/// root.draw(&PathElement::new([(0, 150), (400, 150)], RED))?;
+/// styles.reset(StrokedPrimitive::Path);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StrokeStyles {
+    styles: Rc<RefCell<[Option<VelloStrokeStyle>; 4]>>,
+}
+
+impl StrokeStyles {
+    /// Styles using [`VelloStrokeStyle::new`] for all primitives.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stroke `primitive` with `style`.
+    pub fn set(&self, primitive: StrokedPrimitive, style: VelloStrokeStyle) {
+        self.styles.borrow_mut()[primitive as usize] = Some(style);
+    }
+
+    /// Stroke all primitives with `style`.
+    pub fn set_all(&self, style: VelloStrokeStyle) {
+        self.styles.borrow_mut().fill(Some(style));
+    }
+
+    /// Go back to the default style of `primitive`.
+    pub fn reset(&self, primitive: StrokedPrimitive) {
+        self.styles.borrow_mut()[primitive as usize] = None;
+    }
+
+    /// The style `primitive` is stroked with.
+    pub fn get(&self, primitive: StrokedPrimitive) -> VelloStrokeStyle {
+        self.styles.borrow()[primitive as usize]
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Set the style of `primitive`, `None` meaning its default style, and return the style
+    /// that was set before.
+    pub(crate) fn replace(
+        &self,
+        primitive: StrokedPrimitive,
+        style: Option<VelloStrokeStyle>,
+    ) -> Option<VelloStrokeStyle> {
+        std::mem::replace(&mut self.styles.borrow_mut()[primitive as usize], style)
+    }
+
+    /// The stroke of `primitive` with the given width.
+    pub(crate) fn stroke(&self, primitive: StrokedPrimitive, width: f64) -> Stroke {
+        match &self.styles.borrow()[primitive as usize] {
+            Some(style) => style.to_stroke(width),
+            None => VelloStrokeStyle::new().to_stroke(width),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vello::kurbo::{Cap, Stroke};
+
+    use super::{StrokeStyles, StrokedPrimitive, VelloStrokeStyle};
+
+    #[test]
+    fn shared_styles() {
+        let styles = StrokeStyles::new();
+        let backend_styles = styles.clone();
+        // The stroke drawn before styles were configurable
+        assert_eq!(
+            backend_styles.stroke(StrokedPrimitive::Rect, 2.),
+            Stroke::new(2.).with_end_cap(Cap::Square)
+        );
+
+        let dashed = VelloStrokeStyle::new()
+            .with_caps(Cap::Butt)
+            .with_dashes(1., [6., 4.]);
+        styles.set(StrokedPrimitive::Path, dashed.clone());
+        let stroke = backend_styles.stroke(StrokedPrimitive::Path, 2.);
+        assert_eq!(stroke.width, 2.);
+        assert_eq!((stroke.start_cap, stroke.end_cap), (Cap::Butt, Cap::Butt));
+        assert_eq!(&*stroke.dash_pattern, [6., 4.]);
+        assert_eq!(stroke.dash_offset, 1.);
+        assert_eq!(
+            backend_styles.get(StrokedPrimitive::Line),
+            VelloStrokeStyle::new()
+        );
+
+        styles.reset(StrokedPrimitive::Path);
+        assert_eq!(
+            backend_styles.get(StrokedPrimitive::Path),
+            VelloStrokeStyle::new()
+        );
+    }
+}
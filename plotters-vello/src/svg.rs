@@ -0,0 +1,382 @@
+use std::fmt::Write;
+
+use plotters_backend::{BackendColor, BackendCoord, FontStyle};
+use vello::kurbo::{Cap, Join};
+
+use crate::VelloStrokeStyle;
+use crate::recording::{DisplayList, DrawCommand, RecordedStyle, anchor_name};
+
+impl DisplayList {
+    /// Write the recorded primitives as an SVG document.
+    ///
+    /// Geometry is placed the same way as by [`VelloBackend`](crate::VelloBackend). Text is
+    /// kept as text and drawn with the fonts of the program showing the SVG, bitmaps are
+    /// embedded as PNG images.
+    ///
+    /// This is only available with the `svg` feature enabled.
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_vello::{DisplayList, RecordingBackend};
+    /// let mut list = DisplayList::new((400, 300));
+    /// let root = RecordingBackend::new(&mut list).into_drawing_area();
+    /// root.draw(&Circle::new((200, 150), 50, RED.filled()))?;
+    /// drop(root);
+    ///
+    /// let svg = list.to_svg();
+    /// # assert!(svg.contains("<circle"));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_svg(&self) -> String {
+        let (width, height) = self.size();
+        let mut svg = format!(
+            r#"<svg width="{width}" height="{height}" viewBox="0 0 {width} {height}" xmlns="http://www.w3.org/2000/svg">"#
+        );
+        svg.push('\n');
+        for command in self.commands() {
+            // Writing to a String can't fail
+            let _ = write_command(&mut svg, command);
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn write_command(svg: &mut String, command: &DrawCommand) -> std::fmt::Result {
+    match command {
+        DrawCommand::Pixel {
+            point: (x, y),
+            style,
+        } => writeln!(
+            svg,
+            r#"<rect x="{x}" y="{y}" width="1" height="1"{}/>"#,
+            Fill(&style.color)
+        ),
+        DrawCommand::Line {
+            from,
+            to,
+            style,
+            stroke,
+        } => {
+            let ((x1, y1), (x2, y2)) = (mid(*from), mid(*to));
+            writeln!(
+                svg,
+                r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}"{}/>"#,
+                Stroke(style, stroke)
+            )
+        }
+        DrawCommand::Rect {
+            upper_left,
+            bottom_right,
+            style,
+            fill: true,
+            ..
+        } => {
+            // Plotters doesn't order the corners
+            let (x, y) = (
+                upper_left.0.min(bottom_right.0),
+                upper_left.1.min(bottom_right.1),
+            );
+            let width = (bottom_right.0 - upper_left.0).abs() + 1;
+            let height = (bottom_right.1 - upper_left.1).abs() + 1;
+            writeln!(
+                svg,
+                r#"<rect x="{x}" y="{y}" width="{width}" height="{height}"{}/>"#,
+                Fill(&style.color)
+            )
+        }
+        DrawCommand::Rect {
+            upper_left,
+            bottom_right,
+            style,
+            fill: false,
+            stroke,
+        } => {
+            let (x, y) = mid((
+                upper_left.0.min(bottom_right.0),
+                upper_left.1.min(bottom_right.1),
+            ));
+            let width = (bottom_right.0 - upper_left.0).abs();
+            let height = (bottom_right.1 - upper_left.1).abs();
+            writeln!(
+                svg,
+                r#"<rect x="{x}" y="{y}" width="{width}" height="{height}" fill="none"{}/>"#,
+                Stroke(style, stroke)
+            )
+        }
+        DrawCommand::Path {
+            points,
+            style,
+            stroke,
+        } => writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none"{}/>"#,
+            Points(points),
+            Stroke(style, stroke)
+        ),
+        DrawCommand::Circle {
+            center,
+            radius,
+            style,
+            fill,
+            stroke,
+        } => {
+            let (cx, cy) = mid(*center);
+            write!(svg, r#"<circle cx="{cx}" cy="{cy}" r="{radius}""#)?;
+            if *fill {
+                writeln!(svg, "{}/>", Fill(&style.color))
+            } else {
+                writeln!(svg, r#" fill="none"{}/>"#, Stroke(style, stroke))
+            }
+        }
+        DrawCommand::Polygon { points, style } => writeln!(
+            svg,
+            r#"<polygon points="{}"{}/>"#,
+            Points(points),
+            Fill(&style.color)
+        ),
+        DrawCommand::Text {
+            text,
+            style,
+            pos: (x, y),
+        } => {
+            let (text_anchor, baseline) = anchor_name(style.anchor);
+            write!(
+                svg,
+                r#"<text x="{x}" y="{y}" font-family="{}" font-size="{}" text-anchor="{text_anchor}" dominant-baseline="{baseline}""#,
+                Escaped(&style.family),
+                style.size
+            )?;
+            match style.style {
+                FontStyle::Normal => {}
+                FontStyle::Bold => svg.push_str(r#" font-weight="bold""#),
+                FontStyle::Oblique | FontStyle::Italic => {
+                    write!(svg, r#" font-style="{}""#, style.style.as_str())?
+                }
+            }
+            if style.rotation() != 0 {
+                write!(svg, r#" transform="rotate({} {x} {y})""#, style.rotation())?;
+            }
+            writeln!(svg, "{}>{}</text>", Fill(&style.color), Escaped(text))
+        }
+        DrawCommand::Bitmap {
+            pos: (x, y),
+            size: (width, height),
+            rgba,
+        } => writeln!(
+            svg,
+            r#"<image x="{x}" y="{y}" width="{width}" height="{height}" href="data:image/png;base64,{}"/>"#,
+            base64(&png(*width, *height, rgba))
+        ),
+    }
+}
+
+/// The center of the pixel at `point`, like `plotters_point_to_kurbo_mid`.
+fn mid((x, y): BackendCoord) -> (f64, f64) {
+    (x as f64 + 0.5, y as f64 + 0.5)
+}
+
+/// The `fill` attributes of a solid color.
+struct Fill<'a>(&'a BackendColor);
+
+impl std::fmt::Display for Fill<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (r, g, b) = self.0.rgb;
+        write!(f, r##" fill="#{r:02x}{g:02x}{b:02x}""##)?;
+        if self.0.alpha < 1. {
+            write!(f, r#" fill-opacity="{}""#, self.0.alpha)?;
+        }
+        Ok(())
+    }
+}
+
+/// The `stroke` attributes of an outline.
+struct Stroke<'a>(&'a RecordedStyle, &'a VelloStrokeStyle);
+
+impl std::fmt::Display for Stroke<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Stroke(style, stroke) = self;
+        let (r, g, b) = style.color.rgb;
+        write!(
+            f,
+            r##" stroke="#{r:02x}{g:02x}{b:02x}" stroke-width="{}""##,
+            style.stroke_width
+        )?;
+        if style.color.alpha < 1. {
+            write!(f, r#" stroke-opacity="{}""#, style.color.alpha)?;
+        }
+
+        let join = match stroke.join {
+            Join::Bevel => "bevel",
+            Join::Miter => "miter",
+            Join::Round => "round",
+        };
+        write!(f, r#" stroke-linejoin="{join}""#)?;
+        if stroke.join == Join::Miter {
+            write!(f, r#" stroke-miterlimit="{}""#, stroke.miter_limit)?;
+        }
+        // SVG has a single cap for both ends of a line
+        let cap = match stroke.end_cap {
+            Cap::Butt => "butt",
+            Cap::Square => "square",
+            Cap::Round => "round",
+        };
+        write!(f, r#" stroke-linecap="{cap}""#)?;
+        if !stroke.dash_pattern.is_empty() {
+            let dashes: Vec<String> = stroke.dash_pattern.iter().map(f64::to_string).collect();
+            write!(f, r#" stroke-dasharray="{}""#, dashes.join(" "))?;
+            if stroke.dash_offset != 0. {
+                write!(f, r#" stroke-dashoffset="{}""#, stroke.dash_offset)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The value of a `points` attribute, placing the points like `plotters_path_to_kurbo`.
+struct Points<'a>(&'a [BackendCoord]);
+
+impl std::fmt::Display for Points<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, point) in self.0.iter().enumerate() {
+            let (x, y) = mid(*point);
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{x},{y}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Text with the characters that are special in XML escaped.
+struct Escaped<'a>(&'a str);
+
+impl std::fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '&' => f.write_str("&amp;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encodes RGBA pixels as a PNG image.
+fn png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(rgba))
+        .expect("writing to a Vec can't fail and bitmaps have 4 bytes per pixel");
+    png
+}
+
+/// Encodes `data` with the standard base64 alphabet and padding.
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use plotters::prelude::*;
+
+    use super::{base64, png};
+    use crate::{DisplayList, RecordingBackend};
+
+    #[test]
+    fn write_svg() {
+        let mut list = DisplayList::new((100, 50));
+        {
+            let root = RecordingBackend::new(&mut list).into_drawing_area();
+            root.fill(&WHITE).unwrap();
+            root.draw(&PathElement::new([(0, 10), (20, 30)], RED.mix(0.5)))
+                .unwrap();
+        }
+        RecordingBackend::new(&mut list)
+            .blit_bitmap((1, 2), (1, 1), &[0, 0, 0])
+            .unwrap();
+
+        let svg = list.to_svg();
+        assert!(svg.starts_with(r#"<svg width="100" height="50" viewBox="0 0 100 50""#));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="101" height="51" fill="#ffffff"/>"##));
+        assert!(svg.contains(r##"<polyline points="0.5,10.5 20.5,30.5" fill="none" stroke="#ff0000" stroke-width="1" stroke-opacity="0.5""##));
+        assert!(svg.contains(
+            r#"<image x="1" y="2" width="1" height="1" href="data:image/png;base64,iVBORw0KGgo"#
+        ));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn unordered_rect_corners() {
+        let mut list = DisplayList::new((100, 50));
+        {
+            let root = RecordingBackend::new(&mut list).into_drawing_area();
+            root.draw(&Rectangle::new([(30, 40), (10, 20)], RED.filled()))
+                .unwrap();
+            root.draw(&Rectangle::new([(30, 20), (10, 40)], BLUE))
+                .unwrap();
+        }
+
+        let svg = list.to_svg();
+        assert!(svg.contains(r##"<rect x="10" y="20" width="21" height="21" fill="#ff0000"/>"##));
+        assert!(svg.contains(r#"<rect x="10.5" y="20.5" width="20" height="20" fill="none""#));
+    }
+
+    #[test]
+    fn encode_base64() {
+        // The test vectors of RFC 4648
+        for (data, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64(data.as_bytes()), encoded);
+        }
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let rgba: Vec<u8> = (0..3 * 2 * 4).map(|i| i as u8 * 10).collect();
+        let png = png(3, 2, &rgba);
+
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        assert_eq!(info.bit_depth, png::BitDepth::Eight);
+        assert_eq!(pixels, rgba);
+    }
+}
@@ -0,0 +1,176 @@
+use std::borrow::Cow;
+use std::f64::consts::PI;
+
+use parley::{
+    FontContext, FontStack, FontWeight, GenericFamily, Layout, LayoutContext, PositionedLayoutItem,
+    StyleProperty,
+};
+use plotters_backend::text_anchor::{HPos, VPos};
+use plotters_backend::{BackendCoord, BackendTextStyle, FontFamily, FontStyle, FontTransform};
+use vello::kurbo::{Affine, Vec2};
+use vello::peniko;
+
+use crate::{Canvas, Error, plotters_color_to_peniko};
+
+/// Where the [`FontContext`] used by a [`TextContext`] comes from.
+///
+/// Creating a [`FontContext`] enumerates the system fonts, so an owned context is
+/// only created once the first piece of text is laid out.
+enum FontContextSource<'a> {
+    Owned(Option<Box<FontContext>>),
+    Borrowed(&'a mut FontContext),
+}
+
+impl FontContextSource<'_> {
+    fn get(&mut self) -> &mut FontContext {
+        match self {
+            FontContextSource::Owned(font_ctx) => font_ctx.get_or_insert_with(Default::default),
+            FontContextSource::Borrowed(font_ctx) => font_ctx,
+        }
+    }
+}
+
+/// The parley contexts used to lay out text for a backend.
+pub(crate) struct TextContext<'a> {
+    font_ctx: FontContextSource<'a>,
+    layout_ctx: LayoutContext<peniko::Brush>,
+}
+
+impl<'a> TextContext<'a> {
+    pub(crate) fn new() -> Self {
+        Self {
+            font_ctx: FontContextSource::Owned(None),
+            layout_ctx: LayoutContext::new(),
+        }
+    }
+
+    pub(crate) fn with_font_context(font_ctx: &'a mut FontContext) -> Self {
+        Self {
+            font_ctx: FontContextSource::Borrowed(font_ctx),
+            layout_ctx: LayoutContext::new(),
+        }
+    }
+
+    /// Lay out `text` on a single line using the font described by `style`, with its size
+    /// multiplied by `scale`.
+    pub(crate) fn layout<S: BackendTextStyle>(
+        &mut self,
+        text: &str,
+        style: &S,
+        scale: f64,
+    ) -> Result<Layout<peniko::Brush>, Error> {
+        let size = style.size() * scale;
+        if !(size.is_finite() && size > 0.) {
+            return Err(Error::TextLayout {
+                text: text.to_owned(),
+                reason: format!("font size {size} is not a positive number"),
+            });
+        }
+
+        let family = match style.family() {
+            FontFamily::Serif => parley::FontFamily::Generic(GenericFamily::UiSerif),
+            FontFamily::SansSerif => parley::FontFamily::Generic(GenericFamily::UiSansSerif),
+            FontFamily::Monospace => parley::FontFamily::Generic(GenericFamily::UiMonospace),
+            FontFamily::Name(name) => parley::FontFamily::Named(Cow::Borrowed(name)),
+        };
+        let color = plotters_color_to_peniko(&style.color());
+
+        let mut builder = self
+            .layout_ctx
+            .ranged_builder(self.font_ctx.get(), text, 1.0, true);
+        builder.push_default(StyleProperty::FontSize(size as f32));
+        builder.push_default(StyleProperty::FontStack(FontStack::Single(family)));
+        builder.push_default(match style.style() {
+            FontStyle::Normal => StyleProperty::FontStyle(parley::FontStyle::Normal),
+            FontStyle::Oblique => StyleProperty::FontStyle(parley::FontStyle::Oblique(None)),
+            FontStyle::Italic => StyleProperty::FontStyle(parley::FontStyle::Italic),
+            FontStyle::Bold => StyleProperty::FontWeight(FontWeight::BOLD),
+        });
+        builder.push_default(StyleProperty::Brush(color.into()));
+
+        let mut layout = builder.build(text);
+        layout.break_all_lines(None);
+
+        // Parley silently drops text it can't find any font for
+        let has_glyphs = layout.lines().any(|line| {
+            line.items()
+                .any(|item| matches!(item, PositionedLayoutItem::GlyphRun(_)))
+        });
+        if !has_glyphs && !text.trim().is_empty() {
+            return Err(Error::MissingFont {
+                family: style.family().as_str().to_owned(),
+                text: text.to_owned(),
+            });
+        }
+
+        Ok(layout)
+    }
+}
+
+/// The size of a laid out piece of text, taking the rotation in `style` into account.
+pub(crate) fn text_size<S: BackendTextStyle>(
+    layout: &Layout<peniko::Brush>,
+    style: &S,
+) -> (u32, u32) {
+    let (width, height) = (
+        layout.full_width().ceil() as u32,
+        layout.height().ceil() as u32,
+    );
+
+    match style.transform() {
+        FontTransform::None | FontTransform::Rotate180 => (width, height),
+        FontTransform::Rotate90 | FontTransform::Rotate270 => (height, width),
+    }
+}
+
+/// Computes the transform that places a laid out piece of text at `pos`, honoring
+/// the anchor and rotation given in `style`.
+pub(crate) fn text_transform<S: BackendTextStyle>(
+    layout: &Layout<peniko::Brush>,
+    style: &S,
+    pos: BackendCoord,
+) -> Affine {
+    let (width, height) = (f64::from(layout.full_width()), f64::from(layout.height()));
+
+    // Center on the origin
+    let transform = Affine::translate(Vec2::new(-width / 2., -height / 2.));
+
+    // Rotate based on FontTransform
+    let (width, height, transform) = match style.transform() {
+        FontTransform::None => (width, height, transform),
+        FontTransform::Rotate90 => (height, width, transform.then_rotate(PI / 2.)),
+        FontTransform::Rotate180 => (width, height, transform.then_rotate(PI)),
+        FontTransform::Rotate270 => (height, width, transform.then_rotate(-PI / 2.)),
+    };
+
+    // Move the anchor to the origin
+    let transform = match style.anchor().h_pos {
+        HPos::Left => transform.then_translate(Vec2::new(width / 2., 0.)),
+        HPos::Center => transform,
+        HPos::Right => transform.then_translate(Vec2::new(-width / 2., 0.)),
+    };
+    let transform = match style.anchor().v_pos {
+        VPos::Top => transform.then_translate(Vec2::new(0., height / 2.)),
+        VPos::Center => transform,
+        VPos::Bottom => transform.then_translate(Vec2::new(0., -height / 2.)),
+    };
+
+    // Move to pos
+    transform.then_translate(Vec2::new(f64::from(pos.0), f64::from(pos.1)))
+}
+
+/// Draws the glyphs of a laid out piece of text onto `canvas`.
+pub(crate) fn draw_layout(
+    canvas: &mut impl Canvas,
+    layout: &Layout<peniko::Brush>,
+    transform: Affine,
+    hint: bool,
+) {
+    for line in layout.lines() {
+        for item in line.items() {
+            if let PositionedLayoutItem::GlyphRun(glyph_run) = item {
+                canvas.draw_glyph_run(&glyph_run, transform, hint);
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use masonry::properties::types::Length;
 use plotters::prelude::*;
-use plotters_xilem::plot;
+use plotters_xilem::{CrosshairStyle, TooltipStyle, Viewport, plot};
 use xilem::style::Style;
 use xilem::view::{
     Axis, CrossAxisAlignment, FlexExt, FlexSpacer, Label, MainAxisAlignment, button, flex, label,
@@ -12,27 +12,33 @@ use xilem::{EventLoop, WidgetView, WindowOptions, Xilem};
 fn build_plot_view(mu: &mut f64) -> impl WidgetView<f64> + use<> {
     plot(
         *mu,
-        |_size, data: &mut f64, root| {
+        |ctx, data: &mut f64, root| {
             let μ = *data as f32;
+            let viewport = ctx.viewport().unwrap();
 
             let res = 400;
             let font = FontDesc::new(FontFamily::SansSerif, 16., FontStyle::Normal);
+            // The plot is drawn in device pixels, the font size is scaled by the widget
+            let px = |logical: f64| (logical * ctx.scale_factor()).round() as u32;
 
             let mut chart = ChartBuilder::on(root)
-                .x_label_area_size(30)
-                .y_label_area_size(30)
-                .margin_right(10)
-                .build_cartesian_2d(0.0..1_f32, 0.0..6_f32)
-                .unwrap();
+                .x_label_area_size(px(30.))
+                .y_label_area_size(px(30.))
+                .margin_right(px(10.))
+                .build_cartesian_2d(
+                    viewport.x.start as f32..viewport.x.end as f32,
+                    viewport.y.start as f32..viewport.y.end as f32,
+                )?;
+            ctx.register_chart(&chart);
 
             chart
                 .configure_mesh()
                 .axis_style(RGBColor(28, 28, 28))
                 .x_label_style(font.clone().with_color(WHITE))
                 .y_label_style(font.clone().with_color(WHITE))
-                .draw()
-                .unwrap();
+                .draw()?;
 
+            let (legend_width, legend_stroke) = (px(20.) as i32, px(2.));
             for (σ, idx) in [0.32_f32, 0.56, 1., 1.78, 3.16].into_iter().zip(0..) {
                 let fac = 1. / (σ * std::f32::consts::TAU.sqrt());
                 let color = Palette99::pick(idx);
@@ -43,14 +49,14 @@ fn build_plot_view(mu: &mut f64) -> impl WidgetView<f64> + use<> {
                     (x, y)
                 });
 
+                ctx.register_series(format!("σ = {σ}"), data.clone());
                 chart
-                    .draw_series(LineSeries::new(data, &color))
-                    .unwrap()
+                    .draw_series(LineSeries::new(data, &color))?
                     .label(format!("σ = {σ}"))
                     .legend(move |(x, y)| {
                         PathElement::new(
-                            vec![(x, y), (x + 20, y)],
-                            ShapeStyle::from(&color).stroke_width(2),
+                            vec![(x, y), (x + legend_width, y)],
+                            ShapeStyle::from(&color).stroke_width(legend_stroke),
                         )
                     });
             }
@@ -60,11 +66,15 @@ fn build_plot_view(mu: &mut f64) -> impl WidgetView<f64> + use<> {
                 .background_style(RGBColor(41, 41, 41))
                 .border_style(RGBColor(28, 28, 28))
                 .label_font(font.with_color(WHITE))
-                .draw()
-                .unwrap();
+                .draw()?;
+            Ok(())
         },
         "Logit-Normal plot",
     )
+    .with_pan_zoom(Viewport::new(0.0..1.0, 0.0..6.0))
+    .with_tooltips(TooltipStyle::default())
+    .with_crosshair(CrosshairStyle::default())
+    .with_logical_font_size()
 }
 
 /// A component to make a bigger than usual button
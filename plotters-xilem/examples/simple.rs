@@ -6,38 +6,41 @@ use xilem::{EventLoop, WidgetView, WindowOptions, Xilem};
 fn app_logic(_: &mut ()) -> impl WidgetView<()> + use<> {
     plot(
         (),
-        |_size, _data, root| {
+        |ctx, _data, root| {
             // Code taken from the plotters example: https://github.com/38/plotters#quick-start
-            root.fill(&WHITE).unwrap();
+            root.fill(&WHITE)?;
+            // The plot is drawn in device pixels, the font size is scaled by the widget
+            let px = |logical: f64| (logical * ctx.scale_factor()).round() as u32;
+
             let mut chart = ChartBuilder::on(root)
                 .caption("y=x^2", ("sans-serif", 50).into_font())
-                .margin(5)
-                .margin_right(15)
-                .x_label_area_size(30)
-                .y_label_area_size(30)
-                .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)
-                .unwrap();
+                .margin(px(5.))
+                .margin_right(px(15.))
+                .x_label_area_size(px(30.))
+                .y_label_area_size(px(30.))
+                .build_cartesian_2d(-1f32..1f32, -0.1f32..1f32)?;
 
-            chart.configure_mesh().draw().unwrap();
+            chart.configure_mesh().draw()?;
 
+            let legend_width = px(20.) as i32;
             chart
                 .draw_series(LineSeries::new(
                     (-50..=50).map(|x| x as f32 / 50.0).map(|x| (x, x * x)),
                     &RED,
-                ))
-                .unwrap()
+                ))?
                 .label("y = x^2")
-                .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + legend_width, y)], RED));
 
             chart
                 .configure_series_labels()
                 .background_style(WHITE.mix(0.8))
                 .border_style(BLACK)
-                .draw()
-                .unwrap();
+                .draw()?;
+            Ok(())
         },
         "simple plot",
     )
+    .with_logical_font_size()
 }
 
 fn main() -> Result<(), EventLoopError> {
@@ -10,48 +10,587 @@ Note that this is not directly a plotters backend in the sense described in
 [plotters_backend](https://docs.rs/plotters-backend/latest/plotters_backend/), instead this uses
 the plotters-masonry backend and wraps it in a struct that implements [`xilem_core::View`].
 
-# Limitations
+# Errors
 
-It's currently not possible to propagate errors that might be returned from the plotters API. Right now
-this means you'll probably have to use `.unwrap()` a lot in the closure that you pass to [`Plot::new`],
-or alternatively just log it and shrug.
+The closure passed to [`plot`] returns a `Result`, so errors of the plotters API can be propagated with
+`?`. When the closure fails, the view shows the error message in place of the plot and calls the callback
+given to [`Plot::on_error`].
+
+# Device pixels
+
+Like with the Masonry widget, the plot is drawn in device pixels. [`PlotContext::size`] is in device pixels
+and pixel sizes passed to plotters, e.g. margins or the sizes of label areas, have to be multiplied by
+[`PlotContext::scale_factor`] to look the same on all displays. Fonts can be scaled with
+[`Plot::with_logical_font_size`]. This is a breaking change: earlier versions drew the plot in logical
+pixels.
 
 */
 
-use masonry::core::ArcStr;
-use plotters_masonry::{Plot as PlotWidget, PlotFn};
-use xilem::core::{MessageResult, View, ViewMarker};
+use std::error::Error;
+use std::ops::Range;
+use std::sync::Arc;
+
+use masonry::core::{ArcStr, PointerButton, WidgetId};
+use masonry::kurbo::Size;
+use plotters::coord::Shift;
+use plotters::prelude::DrawingArea;
+pub use plotters_masonry::{
+    CoordMapping, CrosshairStyle, ErrorStyle, HoveredPoint, PlotAction, PlotContext, PlotGroup,
+    PointIndex, StrokeStyles, StrokedPrimitive, TooltipStyle, VelloStrokeStyle, Viewport,
+};
+use plotters_masonry::{MasonryBackend, Plot as PlotWidget};
+use xilem::core::{MessageProxy, MessageResult, View, ViewMarker, ViewPathTracker};
 use xilem::{Pod, ViewCtx};
 
-pub fn plot<Data>(data: Data, plot: PlotFn<Data>, alt_text: impl Into<ArcStr>) -> Plot<Data>
+/// Create a plot view.
+///
+/// `plot` draws the plot using the normal plotters API, see
+/// [`plotters_masonry::Plot::new`] for details. The plot is redrawn whenever `data`
+/// changes. The function itself is only replaced when the key given to
+/// [`Plot::depends_on`] changes, so values captured by the closure that may change
+/// between app logic runs should be part of that key.
+pub fn plot<State, Data, F>(
+    data: Data,
+    plot: F,
+    alt_text: impl Into<ArcStr>,
+) -> Plot<State, Data, F>
 where
     Data: Clone + PartialEq,
+    F: Fn(
+            &PlotContext,
+            &mut Data,
+            &DrawingArea<MasonryBackend, Shift>,
+        ) -> Result<(), Box<dyn Error>>
+        + Send
+        + Sync
+        + 'static,
 {
     Plot {
         data,
-        plot,
+        plot: Arc::new(plot),
+        key: (),
         alt_text: alt_text.into(),
+        pan_zoom: None,
+        viewport: None,
+        tooltips: None,
+        crosshair: None,
+        logical_font_size: false,
+        batching: false,
+        error_style: None,
+        preferred_size: None,
+        aspect_ratio: None,
+        min_size: Size::ZERO,
+        group: None,
+        callbacks: Callbacks {
+            on_click: None,
+            on_hover: None,
+            on_viewport_change: None,
+            on_select: None,
+            on_error: None,
+        },
     }
 }
 
-pub struct Plot<Data> {
+/// The [`View`] created by [`plot`].
+pub struct Plot<State, Data, F, Key = ()> {
     data: Data,
-    plot: PlotFn<Data>,
+    plot: Arc<F>,
+    key: Key,
     alt_text: ArcStr,
+    pan_zoom: Option<Viewport>,
+    viewport: Option<Viewport>,
+    tooltips: Option<TooltipStyle>,
+    crosshair: Option<CrosshairStyle>,
+    logical_font_size: bool,
+    batching: bool,
+    error_style: Option<ErrorStyle>,
+    preferred_size: Option<Size>,
+    aspect_ratio: Option<f64>,
+    min_size: Size,
+    group: Option<PlotGroup>,
+    callbacks: Callbacks<State>,
+}
+
+type Callback<State, Arg> = Box<dyn Fn(&mut State, Arg) + Send + Sync>;
+
+/// The message sent to the view of a plot when the state of its group changed.
+#[derive(Debug)]
+struct SyncGroup;
+
+/// Let the other plots of `group` wake the plot `id` by sending [`SyncGroup`] to the view
+/// currently being built.
+fn set_waker(group: &PlotGroup, ctx: &mut ViewCtx, id: WidgetId) {
+    let proxy = MessageProxy::new(ctx.proxy(), ctx.view_path().into());
+    group.set_waker(id, move || {
+        // The plot is gone if the app stopped
+        let _ = proxy.message(SyncGroup);
+    });
+}
+
+/// The selected x and y ranges and the registered points inside them.
+type Selection = (Range<f64>, Range<f64>, Vec<PointIndex>);
+
+/// The callbacks handling the [`PlotAction`]s of the widget.
+struct Callbacks<State> {
+    on_click: Option<Callback<State, (f64, f64, PointerButton)>>,
+    on_hover: Option<Callback<State, Option<HoveredPoint>>>,
+    on_viewport_change: Option<Callback<State, Viewport>>,
+    on_select: Option<Callback<State, Selection>>,
+    on_error: Option<Callback<State, String>>,
 }
 
-impl<Data> ViewMarker for Plot<Data> {}
+impl<State> Callbacks<State> {
+    /// Call the callback for `action`, returning whether there was one.
+    fn handle(&self, app_state: &mut State, action: PlotAction) -> bool {
+        match action {
+            PlotAction::Clicked {
+                data_x,
+                data_y,
+                button,
+            } => call(&self.on_click, app_state, (data_x, data_y, button)),
+            PlotAction::Hovered(point) => call(&self.on_hover, app_state, point),
+            PlotAction::ViewportChanged(viewport) => {
+                call(&self.on_viewport_change, app_state, viewport)
+            }
+            PlotAction::RangeSelected { x, y, points } => {
+                call(&self.on_select, app_state, (x, y, points))
+            }
+            PlotAction::Error(err) => call(&self.on_error, app_state, err),
+            _ => false,
+        }
+    }
+}
+
+fn call<State, Arg>(
+    callback: &Option<Callback<State, Arg>>,
+    app_state: &mut State,
+    arg: Arg,
+) -> bool {
+    match callback {
+        Some(callback) => {
+            callback(app_state, arg);
+            true
+        }
+        None => false,
+    }
+}
+
+impl<State, Data, F, Key> Plot<State, Data, F, Key> {
+    /// Set the key the plot function depends on.
+    ///
+    /// When the key differs from the one of the previous view, the widget's plot function
+    /// is replaced and the plot is redrawn.
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_xilem::plot;
+    /// # let line_color = RED;
+    /// # let _: plotters_xilem::Plot<(), _, _, _> =
+    /// plot(
+    ///     (),
+    ///     move |_ctx, _data, root| {
+    ///         root.fill(&line_color)?;
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// )
+    /// .depends_on(line_color);
+    /// ```
+    pub fn depends_on<NewKey: PartialEq>(self, key: NewKey) -> Plot<State, Data, F, NewKey> {
+        Plot {
+            data: self.data,
+            plot: self.plot,
+            key,
+            alt_text: self.alt_text,
+            pan_zoom: self.pan_zoom,
+            viewport: self.viewport,
+            tooltips: self.tooltips,
+            crosshair: self.crosshair,
+            logical_font_size: self.logical_font_size,
+            batching: self.batching,
+            error_style: self.error_style,
+            preferred_size: self.preferred_size,
+            aspect_ratio: self.aspect_ratio,
+            min_size: self.min_size,
+            group: self.group,
+            callbacks: self.callbacks,
+        }
+    }
+
+    /// Enable panning and zooming, starting out at the `home` viewport.
+    ///
+    /// See [`plotters_masonry::Plot::with_pan_zoom`] for details. Changing `home` resets
+    /// the viewport.
+    pub fn with_pan_zoom(mut self, home: Viewport) -> Self {
+        self.pan_zoom = Some(home);
+        self
+    }
+
+    /// Control the viewport from the app state.
+    ///
+    /// The plot shows `viewport` and calls `on_change` when the user pans or zooms, which
+    /// should store the new viewport in the app state. When `viewport` differs from the one
+    /// of the previous view, e.g. because another part of the app changed it, the plot
+    /// switches to it. This replaces any callback set with
+    /// [`on_viewport_change`](Self::on_viewport_change).
+    ///
+    /// Pan and zoom are enabled with `viewport` as the home viewport, unless
+    /// [`with_pan_zoom`](Self::with_pan_zoom) is used to set a different one.
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_xilem::{Viewport, plot};
+    /// # use xilem::WidgetView;
+    /// struct AppState {
+    ///     visible: Viewport,
+    /// }
+    ///
+    /// fn plot_view(state: &mut AppState) -> impl WidgetView<AppState> + use<> {
+    ///     plot(
+    ///         (),
+    ///         |ctx, _data, root| {
+    ///             let viewport = ctx.viewport().unwrap();
+    ///             let chart = ChartBuilder::on(&root)
+    ///                 .build_cartesian_2d(viewport.x.clone(), viewport.y.clone())?;
+    ///             ctx.register_chart(&chart);
+    ///             Ok(())
+    ///         },
+    ///         "plot",
+    ///     )
+    ///     .viewport(state.visible.clone(), |state: &mut AppState, viewport| {
+    ///         state.visible = viewport;
+    ///     })
+    /// }
+    /// ```
+    pub fn viewport(
+        mut self,
+        viewport: Viewport,
+        on_change: impl Fn(&mut State, Viewport) + Send + Sync + 'static,
+    ) -> Self {
+        self.viewport = Some(viewport);
+        self.on_viewport_change(on_change)
+    }
+
+    /// Show a tooltip for the registered point closest to the pointer.
+    ///
+    /// See [`plotters_masonry::Plot::with_tooltips`] for details.
+    pub fn with_tooltips(mut self, style: TooltipStyle) -> Self {
+        self.tooltips = Some(style);
+        self
+    }
+
+    /// Show a crosshair following the pointer, with its data coordinates next to the axes.
+    ///
+    /// See [`plotters_masonry::Plot::with_crosshair`] for details.
+    pub fn with_crosshair(mut self, style: CrosshairStyle) -> Self {
+        self.crosshair = Some(style);
+        self
+    }
+
+    /// Scale the fonts of the plot by the scale factor of the window.
+    ///
+    /// See [`plotters_masonry::Plot::with_logical_font_size`] for details.
+    pub fn with_logical_font_size(mut self) -> Self {
+        self.logical_font_size = true;
+        self
+    }
+
+    /// Draw runs of primitives with the same style as a single path.
+    ///
+    /// See [`plotters_masonry::Plot::with_batching`] for details.
+    pub fn with_batching(mut self) -> Self {
+        self.batching = true;
+        self
+    }
+
+    /// Show errors of the plot function in the given style.
+    ///
+    /// See [`plotters_masonry::Plot::with_error_style`] for details.
+    pub fn with_error_style(mut self, style: ErrorStyle) -> Self {
+        self.error_style = Some(style);
+        self
+    }
+
+    /// Ask for `size` instead of all the space the container offers.
+    ///
+    /// See [`plotters_masonry::Plot::with_preferred_size`] for details.
+    pub fn with_preferred_size(mut self, size: Size) -> Self {
+        self.preferred_size = Some(size);
+        self
+    }
+
+    /// Keep the width of the plot at `ratio` times its height.
+    ///
+    /// See [`plotters_masonry::Plot::with_aspect_ratio`] for details.
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_xilem::plot;
+    /// # use xilem::WidgetView;
+    /// # use xilem::view::portal;
+    /// fn report_plot() -> impl WidgetView<()> {
+    ///     // Inside of a scroll view the height follows from the available width
+    ///     portal(
+    ///         plot(
+    ///             (),
+    ///             |_ctx, _data, root| {
+    ///                 root.fill(&WHITE)?;
+    ///                 Ok(())
+    ///             },
+    ///             "plot",
+    ///         )
+    ///         .with_aspect_ratio(16. / 9.),
+    ///     )
+    /// }
+    /// ```
+    pub fn with_aspect_ratio(mut self, ratio: f64) -> Self {
+        self.aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Don't shrink the plot below `size`, unless the container requires it.
+    ///
+    /// See [`plotters_masonry::Plot::with_min_size`].
+    pub fn with_min_size(mut self, size: Size) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Link the x axis and the hover position of the plot with the other plots of `group`.
+    ///
+    /// See [`PlotGroup`] for details. Unlike with the Masonry widget the view registers the
+    /// wakers of its plots, so the other plots are repainted automatically.
+    pub fn with_group(mut self, group: PlotGroup) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Call `on_click` when the user clicks on the plot.
+    ///
+    /// It gets the data coordinates of the click and the button that was pressed. This
+    /// requires the plot function to register its chart with [`PlotContext::register_chart`].
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_xilem::plot;
+    /// struct AppState {
+    ///     selected_x: Option<f64>,
+    /// }
+    ///
+    /// # let _: plotters_xilem::Plot<AppState, _, _> =
+    /// plot(
+    ///     (),
+    ///     |ctx, _data, root| {
+    ///         let chart = ChartBuilder::on(&root)
+    ///             .build_cartesian_2d(0f64..10f64, 0f64..1f64)?;
+    ///         ctx.register_chart(&chart);
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// )
+    /// .on_click(|state: &mut AppState, (x, _y, _button)| {
+    ///     state.selected_x = Some(x);
+    /// });
+    /// ```
+    pub fn on_click(
+        mut self,
+        on_click: impl Fn(&mut State, (f64, f64, PointerButton)) + Send + Sync + 'static,
+    ) -> Self {
+        self.callbacks.on_click = Some(Box::new(on_click));
+        self
+    }
+
+    /// Call `on_hover` when the registered point closest to the pointer changes.
+    ///
+    /// See [`PlotContext::register_series`] on how to register points.
+    pub fn on_hover(
+        mut self,
+        on_hover: impl Fn(&mut State, Option<HoveredPoint>) + Send + Sync + 'static,
+    ) -> Self {
+        self.callbacks.on_hover = Some(Box::new(on_hover));
+        self
+    }
+
+    /// Call `on_viewport_change` when the user pans or zooms the plot.
+    ///
+    /// Pan and zoom are enabled with [`with_pan_zoom`](Self::with_pan_zoom).
+    pub fn on_viewport_change(
+        mut self,
+        on_viewport_change: impl Fn(&mut State, Viewport) + Send + Sync + 'static,
+    ) -> Self {
+        self.callbacks.on_viewport_change = Some(Box::new(on_viewport_change));
+        self
+    }
+
+    /// Let the user select a region of the plot by dragging with shift held, calling
+    /// `on_select` with the selected data ranges and the registered points inside them.
+    ///
+    /// See [`plotters_masonry::Plot::with_selection`] for details.
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_xilem::{PointIndex, plot};
+    /// struct AppState {
+    ///     outliers: Vec<PointIndex>,
+    /// }
+    ///
+    /// # let _: plotters_xilem::Plot<AppState, _, _> =
+    /// plot(
+    ///     vec![(1.0, 2.0), (2.0, 9.0), (3.0, 4.0)],
+    ///     |ctx, data, root| {
+    ///         let chart = ChartBuilder::on(&root)
+    ///             .build_cartesian_2d(0f64..4f64, 0f64..10f64)?;
+    ///         ctx.register_chart(&chart);
+    ///         ctx.register_series("measurements", data.iter().copied());
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// )
+    /// .on_select(|state: &mut AppState, (_x, _y, points)| {
+    ///     state.outliers = points;
+    /// });
+    /// ```
+    pub fn on_select(
+        mut self,
+        on_select: impl Fn(&mut State, (Range<f64>, Range<f64>, Vec<PointIndex>))
+        + Send
+        + Sync
+        + 'static,
+    ) -> Self {
+        self.callbacks.on_select = Some(Box::new(on_select));
+        self
+    }
+
+    /// Call `on_error` with the error message when the plot function returns an error.
+    ///
+    /// The plot shows the message in place of the plot, see
+    /// [`with_error_style`](Self::with_error_style). `on_error` is called once when the error
+    /// occurs, not every time the plot is drawn.
+    ///
+    /// ```rust
+    /// # use plotters::prelude::*;
+    /// # use plotters_xilem::plot;
+    /// struct AppState {
+    ///     range: std::ops::Range<f64>,
+    ///     last_error: Option<String>,
+    /// }
+    ///
+    /// # let state = AppState { range: 0.0..1.0, last_error: None };
+    /// # let _: plotters_xilem::Plot<AppState, _, _> =
+    /// plot(
+    ///     state.range.clone(),
+    ///     |_ctx, range, root| {
+    ///         ChartBuilder::on(&root).build_cartesian_2d(range.clone(), 0f64..1f64)?;
+    ///         Ok(())
+    ///     },
+    ///     "plot",
+    /// )
+    /// .on_error(|state: &mut AppState, err| {
+    ///     state.last_error = Some(err);
+    /// });
+    /// ```
+    pub fn on_error(
+        mut self,
+        on_error: impl Fn(&mut State, String) + Send + Sync + 'static,
+    ) -> Self {
+        self.callbacks.on_error = Some(Box::new(on_error));
+        self
+    }
 
-impl<State, Data: Clone + PartialEq + 'static> View<State, (), ViewCtx> for Plot<Data> {
+    /// The viewport `rebuild` has to show, if any.
+    ///
+    /// Only changes of the app state are followed, the plot may show a different viewport in
+    /// the meantime, e.g. because its group changed the x range. Changing the pan and zoom
+    /// settings resets the plot to its home viewport, so the viewport of the app state is
+    /// shown again afterwards.
+    fn changed_viewport(&self, prev: &Self) -> Option<&Viewport> {
+        let reset = prev.pan_zoom != self.pan_zoom;
+        self.viewport
+            .as_ref()
+            .filter(|_| reset || prev.viewport != self.viewport)
+    }
+}
+
+impl<State, Data, F, Key> Plot<State, Data, F, Key>
+where
+    Data: 'static,
+    F: Fn(
+            &PlotContext,
+            &mut Data,
+            &DrawingArea<MasonryBackend, Shift>,
+        ) -> Result<(), Box<dyn Error>>
+        + 'static,
+{
+    fn plot_fn(
+        &self,
+    ) -> impl Fn(
+        &PlotContext,
+        &mut Data,
+        &DrawingArea<MasonryBackend, Shift>,
+    ) -> Result<(), Box<dyn Error>>
+    + 'static {
+        let plot = self.plot.clone();
+        move |ctx, data, root| plot(ctx, data, root)
+    }
+}
+
+impl<State, Data, F, Key> ViewMarker for Plot<State, Data, F, Key> {}
+
+impl<State, Data, F, Key> View<State, (), ViewCtx> for Plot<State, Data, F, Key>
+where
+    State: 'static,
+    Data: Clone + PartialEq + 'static,
+    F: Fn(
+            &PlotContext,
+            &mut Data,
+            &DrawingArea<MasonryBackend, Shift>,
+        ) -> Result<(), Box<dyn Error>>
+        + 'static,
+    Key: PartialEq + 'static,
+{
     type Element = Pod<PlotWidget<Data>>;
     type ViewState = ();
 
     fn build(&self, ctx: &mut ViewCtx, _app_state: &mut State) -> (Self::Element, Self::ViewState) {
-        let widget_pod = ctx.create_pod(PlotWidget::new(
-            self.data.clone(),
-            self.plot,
-            self.alt_text.clone(),
-        ));
+        let mut widget = PlotWidget::new(self.data.clone(), self.plot_fn(), self.alt_text.clone());
+        if let Some(home) = &self.pan_zoom {
+            widget = widget.with_pan_zoom(home.clone());
+        }
+        if let Some(viewport) = &self.viewport {
+            widget = widget.with_viewport(viewport.clone());
+        }
+        if let Some(style) = &self.tooltips {
+            widget = widget.with_tooltips(style.clone());
+        }
+        if let Some(style) = &self.crosshair {
+            widget = widget.with_crosshair(style.clone());
+        }
+        if self.logical_font_size {
+            widget = widget.with_logical_font_size();
+        }
+        if self.batching {
+            widget = widget.with_batching();
+        }
+        if let Some(style) = &self.error_style {
+            widget = widget.with_error_style(style.clone());
+        }
+        if let Some(size) = self.preferred_size {
+            widget = widget.with_preferred_size(size);
+        }
+        if let Some(ratio) = self.aspect_ratio {
+            widget = widget.with_aspect_ratio(ratio);
+        }
+        widget = widget.with_min_size(self.min_size);
+        if let Some(group) = &self.group {
+            widget = widget.with_group(group.clone());
+        }
+        if self.callbacks.on_select.is_some() {
+            widget = widget.with_selection();
+        }
+        let widget_pod = ctx.with_action_widget(|ctx| ctx.create_pod(widget));
+        if let Some(group) = &self.group {
+            set_waker(group, ctx, widget_pod.new_widget.id());
+        }
         (widget_pod, ())
     }
 
@@ -59,15 +598,55 @@ impl<State, Data: Clone + PartialEq + 'static> View<State, (), ViewCtx> for Plot
         &self,
         prev: &Self,
         _view_state: &mut Self::ViewState,
-        _ctx: &mut ViewCtx,
+        ctx: &mut ViewCtx,
         mut element: xilem::core::Mut<'_, Self::Element>,
         _app_state: &mut State,
     ) {
         if prev.data != self.data {
             PlotWidget::set_data(&mut element, self.data.clone());
         }
-        if !std::ptr::fn_addr_eq(prev.plot, self.plot) {
-            PlotWidget::set_plot(&mut element, self.plot);
+        if prev.key != self.key {
+            PlotWidget::set_plot(&mut element, self.plot_fn());
+        }
+        if prev.pan_zoom != self.pan_zoom {
+            PlotWidget::set_pan_zoom(&mut element, self.pan_zoom.clone());
+        }
+        if let Some(viewport) = self.changed_viewport(prev) {
+            PlotWidget::set_viewport(&mut element, viewport.clone());
+        }
+        if prev.tooltips != self.tooltips {
+            PlotWidget::set_tooltips(&mut element, self.tooltips.clone());
+        }
+        if prev.crosshair != self.crosshair {
+            PlotWidget::set_crosshair(&mut element, self.crosshair.clone());
+        }
+        if prev.logical_font_size != self.logical_font_size {
+            PlotWidget::set_logical_font_size(&mut element, self.logical_font_size);
+        }
+        if prev.batching != self.batching {
+            PlotWidget::set_batching(&mut element, self.batching);
+        }
+        if prev.error_style != self.error_style {
+            PlotWidget::set_error_style(&mut element, self.error_style.clone().unwrap_or_default());
+        }
+        if prev.preferred_size != self.preferred_size {
+            PlotWidget::set_preferred_size(&mut element, self.preferred_size);
+        }
+        if prev.aspect_ratio != self.aspect_ratio {
+            PlotWidget::set_aspect_ratio(&mut element, self.aspect_ratio);
+        }
+        if prev.min_size != self.min_size {
+            PlotWidget::set_min_size(&mut element, self.min_size);
+        }
+        if prev.group != self.group {
+            PlotWidget::set_group(&mut element, self.group.clone());
+            if let Some(group) = &self.group {
+                set_waker(group, ctx, element.ctx.widget_id());
+            }
+        }
+        let selection = self.callbacks.on_select.is_some();
+        if prev.callbacks.on_select.is_some() != selection {
+            PlotWidget::set_selection(&mut element, selection);
         }
     }
 
@@ -83,13 +662,76 @@ impl<State, Data: Clone + PartialEq + 'static> View<State, (), ViewCtx> for Plot
     fn message(
         &self,
         _view_state: &mut Self::ViewState,
-        _message: &mut xilem::core::MessageContext,
-        _element: xilem::core::Mut<'_, Self::Element>,
-        _app_state: &mut State,
+        message: &mut xilem::core::MessageContext,
+        mut element: xilem::core::Mut<'_, Self::Element>,
+        app_state: &mut State,
     ) -> xilem::core::MessageResult<()> {
-        tracing::error!(
-            "Message arrived in Plot::message, but Plot doesn't consume any messages, this is a bug"
+        if message.take_message::<SyncGroup>().is_some() {
+            PlotWidget::sync_group(&mut element);
+            return MessageResult::Nop;
+        }
+        match message.take_message::<PlotAction>() {
+            Some(action) => {
+                if self.callbacks.handle(app_state, *action) {
+                    MessageResult::Action(())
+                } else {
+                    MessageResult::Nop
+                }
+            }
+            None => {
+                tracing::error!(
+                    "Wrong message type in Plot::message: {message:?} expected {}",
+                    std::any::type_name::<PlotAction>()
+                );
+                MessageResult::Stale
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use plotters::coord::Shift;
+    use plotters::prelude::DrawingArea;
+    use plotters_masonry::MasonryBackend;
+
+    use super::{PlotContext, Viewport, plot};
+
+    fn draw(
+        _: &PlotContext,
+        _: &mut (),
+        _: &DrawingArea<MasonryBackend, Shift>,
+    ) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    #[test]
+    fn viewport_after_pan_zoom_change() {
+        let home = Viewport::new(0.0..1.0, 0.0..1.0);
+        let other_home = Viewport::new(0.0..2.0, 0.0..2.0);
+        let shown = Viewport::new(0.5..1.0, 0.0..1.0);
+        let view = |home: &Viewport, viewport: &Viewport| {
+            plot::<(), _, _>((), draw, "plot")
+                .with_pan_zoom(home.clone())
+                .viewport(viewport.clone(), |_, _| {})
+        };
+
+        let prev = view(&home, &shown);
+        assert_eq!(view(&home, &shown).changed_viewport(&prev), None);
+        assert_eq!(
+            view(&home, &home).changed_viewport(&prev),
+            Some(&home),
+            "the viewport of the app state changed"
+        );
+        assert_eq!(
+            view(&other_home, &shown).changed_viewport(&prev),
+            Some(&shown),
+            "changing the home viewport must not drop the controlled viewport"
         );
-        MessageResult::Stale
+
+        let uncontrolled = plot::<(), _, _>((), draw, "plot").with_pan_zoom(other_home);
+        assert_eq!(uncontrolled.changed_viewport(&prev), None);
     }
 }